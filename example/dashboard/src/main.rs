@@ -0,0 +1,162 @@
+//! Live terminal view of `SensData`.
+//!
+//! Sends `mani_ctrl_disable` packets so the robot keeps streaming without
+//! being commanded. Rows turn red on a driver error and yellow when hot or
+//! close to a joint limit. Logs go to `dashboard.log`.
+
+mod stats;
+mod term;
+mod view;
+
+use std::io::Write;
+use std::sync::mpsc::TryRecvError;
+use std::time::{Duration as StdDuration, Instant, SystemTime, UNIX_EPOCH};
+
+use tokio::time::{Duration, interval};
+use tracing::{Level, info, warn};
+
+use openloong_sdk_rust::param::LoongManiParam;
+use openloong_sdk_rust::sdk::LoongManiSdk;
+use openloong_sdk_rust::sdk::ctrl::InCharge;
+use openloong_sdk_rust::sdk::sens::SensData;
+
+use stats::PacketStats;
+use term::RawTerminal;
+use view::Joints;
+
+const PERIOD: Duration = Duration::from_millis(10);
+/// Redraw every this many periods.
+const REDRAW: u64 = 10;
+/// Packets read per period at most.
+const MAX_RECV: usize = 64;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_ansi(false)
+        .with_writer(std::sync::Mutex::new(std::fs::File::create(
+            "dashboard.log",
+        )?))
+        .init();
+    let param = LoongManiParam::read_from_toml()?;
+    let joints = joints(&param);
+    let stats = PacketStats::new();
+    let mut sdk = LoongManiSdk::builder()
+        .param(param)
+        .logger(stats.clone())
+        .build()?;
+    sdk.ctrl_mut().set_in_charge(InCharge::ManiCtrlDisable);
+
+    let _terminal = RawTerminal::enter()?;
+    let keys = term::spawn_reader();
+    let mut ticker = interval(PERIOD);
+    let mut paused = false;
+    let mut shown = [String::new(), String::new()];
+    // the SensData behind `shown`, so a paused snapshot matches the screen
+    let mut shown_sens = sdk.sens().clone();
+    let mut status: (String, Instant) = (String::new(), Instant::now());
+
+    for frame in 0_u64.. {
+        let mut redraw = frame % REDRAW == 0;
+        loop {
+            let key = match keys.try_recv() {
+                Ok(key) => key,
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => b'q',
+            };
+            match key {
+                b'q' | 0x03 => return Ok(()),
+                b'p' | b' ' => paused = !paused,
+                b'd' => {
+                    let message = match snapshot(&shown[0], &shown_sens) {
+                        Ok(path) => format!("snapshot written to {}", path),
+                        Err(e) => format!("snapshot failed: {}", e),
+                    };
+                    info!("{}", message);
+                    status = (message, Instant::now());
+                }
+                _ => continue,
+            }
+            redraw = true;
+        }
+
+        if let Err(e) = sdk.send() {
+            warn!("Send failed: {}", e);
+        }
+        for _ in 0..MAX_RECV {
+            let count = stats.count();
+            sdk.recv()?;
+            if stats.count() == count {
+                break;
+            }
+        }
+
+        if redraw {
+            if status.1.elapsed() > StdDuration::from_secs(3) {
+                status.0.clear();
+            }
+            // a paused screen keeps its frame, only the status line changes
+            if !paused {
+                let summary = stats.summary();
+                shown_sens = sdk.sens().clone();
+                shown = [
+                    view::render(&shown_sens, &joints, &summary, false),
+                    view::render(&shown_sens, &joints, &summary, true),
+                ];
+            }
+            let line = if paused { "PAUSED" } else { "" };
+            draw(&format!("{}   {} {}", shown[1], line, status.0))?;
+        }
+        ticker.tick().await;
+    }
+    Ok(())
+}
+
+fn draw(screen: &str) -> std::io::Result<()> {
+    let mut out = std::io::stdout().lock();
+    write!(out, "\x1b[H")?;
+    for line in screen.lines() {
+        write!(out, "{}\x1b[K\r\n", line)?;
+    }
+    write!(out, "\x1b[J")?;
+    out.flush()
+}
+
+/// Write the frame on screen and the full `SensData` it was drawn from to a file.
+fn snapshot(shown: &str, sens: &SensData) -> std::io::Result<String> {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let path = format!("sens-{}.txt", secs);
+    let text = format!("{}\n\n# SensData\n{}", shown, sens);
+    std::fs::write(&path, text)?;
+    Ok(path)
+}
+
+fn joints(param: &LoongManiParam) -> Joints {
+    if let Some(model) = param.model() {
+        return Joints {
+            names: model.joint_names().iter().map(|s| s.to_string()).collect(),
+            limits: model.joint_limits(),
+        };
+    }
+    let k = param.kinematics();
+    let mut joints = Joints {
+        names: Vec::new(),
+        limits: Vec::new(),
+    };
+    for (prefix, chain) in [
+        ("l_", &k.left_arm),
+        ("r_", &k.right_arm),
+        ("", &k.neck),
+        ("", &k.lumbar),
+    ] {
+        for joint in &chain.joints {
+            joints.names.push(format!("{}{}", prefix, joint.name));
+            joints.limits.push([joint.min, joint.max]);
+        }
+    }
+    joints
+}
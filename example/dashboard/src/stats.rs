@@ -0,0 +1,98 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use openloong_sdk_rust::sdk::logger::PacketLogger;
+use openloong_sdk_rust::sdk::sens::SensData;
+
+/// Arrivals kept for the rate, and for the best delay seen.
+const RATE_WINDOW: Duration = Duration::from_secs(1);
+const DELAY_WINDOW: Duration = Duration::from_secs(10);
+
+/// Packet timing, fed by the SDK as its logger.
+#[derive(Clone)]
+pub struct PacketStats(Arc<Mutex<Inner>>);
+
+struct Inner {
+    start: Instant,
+    count: u64,
+    arrivals: VecDeque<Instant>,
+    /// (arrival, local clock minus robot timestamp), seconds
+    offsets: VecDeque<(Instant, f64)>,
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Summary {
+    pub count: u64,
+    /// Packets per second over the last second.
+    pub rate: f64,
+    /// Time since the last packet.
+    pub gap: Option<Duration>,
+    /// One-way delay of the last packet above the best of the last ten
+    /// seconds, seconds. The clocks are not synchronized, so this is jitter
+    /// on top of the unknown base latency.
+    pub delay: Option<f64>,
+}
+
+impl PacketStats {
+    pub fn new() -> Self {
+        Self(Arc::new(Mutex::new(Inner {
+            start: Instant::now(),
+            count: 0,
+            arrivals: VecDeque::new(),
+            offsets: VecDeque::new(),
+        })))
+    }
+
+    pub fn count(&self) -> u64 {
+        self.0.lock().unwrap().count
+    }
+
+    pub fn summary(&self) -> Summary {
+        let mut inner = self.0.lock().unwrap();
+        let now = Instant::now();
+        while inner
+            .arrivals
+            .front()
+            .is_some_and(|t| now - *t > RATE_WINDOW)
+        {
+            inner.arrivals.pop_front();
+        }
+        let best = inner
+            .offsets
+            .iter()
+            .map(|(_, o)| *o)
+            .fold(f64::INFINITY, f64::min);
+        Summary {
+            count: inner.count,
+            rate: inner.arrivals.len() as f64 / RATE_WINDOW.as_secs_f64(),
+            gap: inner.arrivals.back().map(|t| now - *t),
+            delay: inner.offsets.back().map(|(_, o)| o - best),
+        }
+    }
+}
+
+impl PacketLogger for PacketStats {
+    fn received(&self, sens: &SensData) {
+        let mut inner = self.0.lock().unwrap();
+        let now = Instant::now();
+        let offset = (now - inner.start).as_secs_f64() - sens.timestamp;
+        inner.count += 1;
+        inner.arrivals.push_back(now);
+        inner.offsets.push_back((now, offset));
+        while inner
+            .arrivals
+            .front()
+            .is_some_and(|t| now - *t > RATE_WINDOW)
+        {
+            inner.arrivals.pop_front();
+        }
+        while inner
+            .offsets
+            .front()
+            .is_some_and(|(t, _)| now - *t > DELAY_WINDOW)
+        {
+            inner.offsets.pop_front();
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use std::io::{Read, Write};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{Receiver, channel};
+
+/// Full-screen, unbuffered and unechoed terminal for as long as it lives.
+pub struct RawTerminal {
+    saved: String,
+}
+
+impl RawTerminal {
+    pub fn enter() -> std::io::Result<Self> {
+        let saved = Command::new("stty")
+            .arg("-g")
+            .stdin(Stdio::inherit())
+            .output()?;
+        let saved = String::from_utf8_lossy(&saved.stdout).trim().to_string();
+        stty(&["-icanon", "-echo", "-isig", "min", "1"])?;
+        // alternate screen, cursor hidden
+        print!("\x1b[?1049h\x1b[?25l");
+        std::io::stdout().flush()?;
+        Ok(Self { saved })
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        print!("\x1b[?25h\x1b[?1049l");
+        let _ = std::io::stdout().flush();
+        let _ = stty(&[&self.saved]);
+    }
+}
+
+fn stty(args: &[&str]) -> std::io::Result<()> {
+    let status = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(std::io::Error::other("stty failed, is stdin a terminal?"))
+    }
+}
+
+/// Forward stdin bytes from a background thread; ctrl-c arrives as 0x03.
+pub fn spawn_reader() -> Receiver<u8> {
+    let (tx, rx) = channel();
+    std::thread::spawn(move || {
+        for b in std::io::stdin().lock().bytes() {
+            let Ok(b) = b else { break };
+            if tx.send(b).is_err() {
+                break;
+            }
+        }
+    });
+    rx
+}
@@ -0,0 +1,146 @@
+use std::fmt::Write;
+
+use openloong_sdk_rust::sdk::sens::SensData;
+
+use crate::stats::Summary;
+
+/// Driver temperature shown as a warning, degrees C.
+pub const TEMP_WARN: i16 = 70;
+/// Distance to a joint limit shown as a warning, rad.
+pub const LIMIT_WARN: f32 = 0.05;
+
+/// Names and limits of the `act_j` entries.
+pub struct Joints {
+    pub names: Vec<String>,
+    pub limits: Vec<[f64; 2]>,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Level {
+    Ok,
+    Warn,
+    Fault,
+}
+
+/// One screen of `sens`. Without `color` the text has no escape codes, for
+/// snapshots.
+pub fn render(sens: &SensData, joints: &Joints, stats: &Summary, color: bool) -> String {
+    let paint = |level: Level, text: String| match (color, level) {
+        (true, Level::Fault) => format!("\x1b[1;31m{}\x1b[0m", text),
+        (true, Level::Warn) => format!("\x1b[33m{}\x1b[0m", text),
+        _ => text,
+    };
+    let mut out = String::new();
+
+    let gap = stats.gap.map_or("-".to_string(), |g| {
+        format!("{:.0} ms", g.as_secs_f64() * 1e3)
+    });
+    let delay = stats
+        .delay
+        .map_or("-".to_string(), |d| format!("+{:.1} ms", d * 1e3));
+    let stale = stats.gap.is_none_or(|g| g.as_millis() > 200);
+    let _ = writeln!(
+        out,
+        "plan {:<16} state {:?}  t {:.3}  {}  gap {}  delay {}  packets {}",
+        sens.plan_name,
+        sens.state,
+        sens.timestamp,
+        paint(
+            if stale { Level::Fault } else { Level::Ok },
+            format!("{:.0} Hz", stats.rate)
+        ),
+        gap,
+        delay,
+        stats.count,
+    );
+    let _ = writeln!(
+        out,
+        "imu  rpy {}  gyr {}  acc {}",
+        vec3(&sens.rpy),
+        vec3(&sens.gyr),
+        vec3(&sens.acc)
+    );
+    let _ = writeln!(out);
+
+    let _ = writeln!(
+        out,
+        "{:<20} {:>8} {:>8} {:>8} {:>8} {:>8} {:>5} {:>5} {:>5}",
+        "joint", "pos", "tgt", "vel", "torque", "tgt_t", "temp", "state", "err"
+    );
+    for i in 0..sens.act_j.len() {
+        let name = joints.names.get(i).map_or("", String::as_str);
+        let q = sens.act_j[i];
+        let near_limit = joints
+            .limits
+            .get(i)
+            .is_some_and(|[min, max]| q < *min as f32 + LIMIT_WARN || q > *max as f32 - LIMIT_WARN);
+        let err = sens.drv_err.get(i).copied().unwrap_or(0);
+        let temp = sens.drv_temp.get(i).copied().unwrap_or(0);
+        let level = if err != 0 {
+            Level::Fault
+        } else if temp >= TEMP_WARN || near_limit {
+            Level::Warn
+        } else {
+            Level::Ok
+        };
+        let row = format!(
+            "{:<20} {:>+8.3} {:>+8.3} {:>+8.3} {:>+8.2} {:>+8.2} {:>5} {:>5} {:>5}",
+            name,
+            q,
+            sens.tgt_j.get(i).copied().unwrap_or(0.0),
+            sens.act_w.get(i).copied().unwrap_or(0.0),
+            sens.act_t.get(i).copied().unwrap_or(0.0),
+            sens.tgt_t.get(i).copied().unwrap_or(0.0),
+            temp,
+            sens.drv_state.get(i).copied().unwrap_or(0),
+            err,
+        );
+        let _ = writeln!(out, "{}", paint(level, row));
+    }
+    let _ = writeln!(out);
+
+    let _ = writeln!(
+        out,
+        "{:<20} {:>8} {:>8} {:>8} {:>8} {:>8} {:>8}",
+        "tip", "x", "y", "z", "roll", "pitch", "yaw"
+    );
+    for (i, side) in ["left", "right"].iter().enumerate() {
+        for (kind, tip) in [
+            ("act", &sens.act_tip_p_rpy2b[i]),
+            ("tgt", &sens.tgt_tip_p_rpy2b[i]),
+        ] {
+            let _ = write!(out, "{:<20}", format!("{} {}", side, kind));
+            for v in tip {
+                let _ = write!(out, " {:>+8.3}", v);
+            }
+            let _ = writeln!(out);
+        }
+    }
+    let _ = writeln!(out);
+
+    for (side, act, tgt) in [
+        ("left", &sens.act_finger_left, &sens.tgt_finger_left),
+        ("right", &sens.act_finger_right, &sens.tgt_finger_right),
+    ] {
+        let list = |v: &[f32]| {
+            v.iter()
+                .map(|x| format!("{:5.1}", x))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        let _ = writeln!(
+            out,
+            "fingers {:<6} act [{}]  tgt [{}]",
+            side,
+            list(&act.to_vec()),
+            list(&tgt.to_vec())
+        );
+    }
+    let _ = writeln!(out);
+    let _ = write!(out, "p pause  d snapshot  q quit");
+    out
+}
+
+fn vec3(v: &[f32; 3]) -> String {
+    format!("[{:+.3} {:+.3} {:+.3}]", v[0], v[1], v[2])
+}
@@ -4,7 +4,8 @@ use ndarray::prelude::*;
 use tokio::time::{Duration, interval};
 use tracing::{Level, info};
 
-use openloong_sdk_rust::{param::LoongManiParam, sdk::LoongManiSdk};
+use openloong_sdk_rust::param::LoongManiParam;
+use openloong_sdk_rust::sdk::{LoongManiSdk, arm::Side};
 
 // use tokio interval to control the loop rate
 #[tokio::main]
@@ -13,14 +14,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .with_max_level(Level::DEBUG)
         .init();
     let param = LoongManiParam::read_from_toml().unwrap();
-    let mut sdk = LoongManiSdk::from_param(&param);
+    let mut sdk = LoongManiSdk::from_param(&param)?;
     let mut arm_cmd_data = array![
         [0.4, 0.4, 0.1, 0.0, 0.0, 0.0, 0.5],
         [0.2, -0.4, 0.1, 0.0, 0.0, 0.0, 0.5]
     ];
-    let finger_dof = sdk.ctrl().finger_dof().ok_or("Failed to get finger dof")? as usize;
-    let mut finger_left_data = Array1::<f32>::zeros(finger_dof);
-    let mut finger_right_data = Array1::<f32>::zeros(finger_dof);
+    let mut finger_left_data = Array1::<f32>::zeros(sdk.ctrl().finger_dof_of(Side::Left) as usize);
+    let mut finger_right_data =
+        Array1::<f32>::zeros(sdk.ctrl().finger_dof_of(Side::Right) as usize);
 
     // use tokio interval to control the loop rate
     let duration = Duration::from_millis(20);
@@ -3,7 +3,8 @@
 use ndarray::prelude::*;
 use tracing::{Level, info};
 
-use openloong_sdk_rust::{param::LoongManiParam, sdk::LoongManiSdk};
+use openloong_sdk_rust::param::LoongManiParam;
+use openloong_sdk_rust::sdk::{LoongManiSdk, arm::Side};
 
 // use tokio interval to control the loop rate
 #[tokio::main]
@@ -12,16 +13,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .with_max_level(Level::DEBUG)
         .init();
     let param = LoongManiParam::read_from_toml().unwrap();
-    let mut sdk = LoongManiSdk::from_param(&param);
+    let mut sdk = LoongManiSdk::from_param(&param)?;
     let (x, y, z, r, yaw, p, arm_angle) = (0.4, 0.4, 0.0, 0.0, 0.0, 0.0, 0.5);
 
     let arm_cmd_data = array![
         [x, y, z, r, yaw, p, arm_angle],
         [x, -y, z, r, yaw, p, arm_angle],
     ];
-    let finger_dof = sdk.ctrl().finger_dof().ok_or("Failed to get finger dof")? as usize;
-    let finger_left_data = Array1::<f32>::zeros(finger_dof);
-    let finger_right_data = Array1::<f32>::zeros(finger_dof);
+    let finger_left_data = Array1::<f32>::zeros(sdk.ctrl().finger_dof_of(Side::Left) as usize);
+    let finger_right_data = Array1::<f32>::zeros(sdk.ctrl().finger_dof_of(Side::Right) as usize);
 
     let mut frame = 0_u32;
     sdk.ctrl_mut().set_arm_cmd(arm_cmd_data.clone());
@@ -0,0 +1,183 @@
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{Receiver, channel};
+
+use openloong_sdk_rust::sdk::arm::{Axis, Side};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Key {
+    Char(char),
+    Up,
+    Down,
+    Left,
+    Right,
+    /// ESC on its own, not the start of an arrow key.
+    Esc,
+    CtrlC,
+}
+
+/// What a key does. Moves are applied while the key is held.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Action {
+    /// Jog `axis` of one arm in direction `dir`.
+    Move {
+        side: Side,
+        axis: Axis,
+        dir: i8,
+    },
+    /// Close (+1) or open (-1) the hand.
+    Finger {
+        side: Side,
+        dir: i8,
+    },
+    /// Neck joint 0 = yaw, 1 = pitch.
+    Neck {
+        axis: usize,
+        dir: i8,
+    },
+    /// Hand the arms to the robot's handheld controller and back.
+    Gamepad,
+    Faster,
+    Slower,
+    Stop,
+    Quit,
+}
+
+pub const HELP: &str = "\
+left arm   w/s x  a/d y  q/e z   W/S pitch  A/D yaw  Q/E roll   z/x close/open
+right arm  i/k x  j/l y  u/o z   I/K pitch  J/L yaw  U/O roll   n/m close/open
+neck       arrow keys          speed  +/-     stop  space     quit  ctrl-c
+gamepad    g  arms and hands follow the handheld controller, [joystick] in param.toml
+";
+
+pub fn action(key: Key) -> Option<Action> {
+    let mv = |side, axis, dir| Some(Action::Move { side, axis, dir });
+    let finger = |side, dir| Some(Action::Finger { side, dir });
+    let neck = |axis, dir| Some(Action::Neck { axis, dir });
+    match key {
+        Key::Char(c) => match c {
+            'w' => mv(Side::Left, Axis::X, 1),
+            's' => mv(Side::Left, Axis::X, -1),
+            'a' => mv(Side::Left, Axis::Y, 1),
+            'd' => mv(Side::Left, Axis::Y, -1),
+            'q' => mv(Side::Left, Axis::Z, 1),
+            'e' => mv(Side::Left, Axis::Z, -1),
+            'Q' => mv(Side::Left, Axis::Roll, 1),
+            'E' => mv(Side::Left, Axis::Roll, -1),
+            'W' => mv(Side::Left, Axis::Pitch, 1),
+            'S' => mv(Side::Left, Axis::Pitch, -1),
+            'A' => mv(Side::Left, Axis::Yaw, 1),
+            'D' => mv(Side::Left, Axis::Yaw, -1),
+            'i' => mv(Side::Right, Axis::X, 1),
+            'k' => mv(Side::Right, Axis::X, -1),
+            'j' => mv(Side::Right, Axis::Y, 1),
+            'l' => mv(Side::Right, Axis::Y, -1),
+            'u' => mv(Side::Right, Axis::Z, 1),
+            'o' => mv(Side::Right, Axis::Z, -1),
+            'U' => mv(Side::Right, Axis::Roll, 1),
+            'O' => mv(Side::Right, Axis::Roll, -1),
+            'I' => mv(Side::Right, Axis::Pitch, 1),
+            'K' => mv(Side::Right, Axis::Pitch, -1),
+            'J' => mv(Side::Right, Axis::Yaw, 1),
+            'L' => mv(Side::Right, Axis::Yaw, -1),
+            'z' => finger(Side::Left, 1),
+            'x' => finger(Side::Left, -1),
+            'n' => finger(Side::Right, 1),
+            'm' => finger(Side::Right, -1),
+            '+' | '=' => Some(Action::Faster),
+            '-' => Some(Action::Slower),
+            ' ' => Some(Action::Stop),
+            'g' => Some(Action::Gamepad),
+            _ => None,
+        },
+        Key::Left => neck(0, 1),
+        Key::Right => neck(0, -1),
+        Key::Up => neck(1, -1),
+        Key::Down => neck(1, 1),
+        Key::CtrlC => Some(Action::Quit),
+        Key::Esc => None,
+    }
+}
+
+/// Unbuffered, unechoed terminal input for as long as it lives.
+pub struct RawTerminal {
+    saved: String,
+}
+
+impl RawTerminal {
+    pub fn enter() -> std::io::Result<Self> {
+        let saved = Command::new("stty")
+            .arg("-g")
+            .stdin(Stdio::inherit())
+            .output()?;
+        let saved = String::from_utf8_lossy(&saved.stdout).trim().to_string();
+        stty(&["-icanon", "-echo", "-isig", "min", "1"])?;
+        Ok(Self { saved })
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        let _ = stty(&[&self.saved]);
+        println!();
+    }
+}
+
+fn stty(args: &[&str]) -> std::io::Result<()> {
+    let status = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(std::io::Error::other("stty failed, is stdin a terminal?"))
+    }
+}
+
+/// Keys in one read from the terminal. The terminal writes an escape
+/// sequence in one go, so an ESC that ends the read was pressed on its own.
+fn decode(bytes: &[u8]) -> Vec<Key> {
+    let mut keys = Vec::new();
+    let mut rest = bytes;
+    while let Some((&b, tail)) = rest.split_first() {
+        rest = tail;
+        let key = match b {
+            0x03 => Key::CtrlC,
+            // arrow keys arrive as ESC [ A..D
+            0x1b => match rest {
+                [b'[', code, tail @ ..] => {
+                    rest = tail;
+                    match code {
+                        b'A' => Key::Up,
+                        b'B' => Key::Down,
+                        b'C' => Key::Right,
+                        b'D' => Key::Left,
+                        _ => continue,
+                    }
+                }
+                _ => Key::Esc,
+            },
+            b => Key::Char(b as char),
+        };
+        keys.push(key);
+    }
+    keys
+}
+
+/// Decode stdin on a background thread.
+pub fn spawn_reader() -> Receiver<Key> {
+    let (tx, rx) = channel();
+    std::thread::spawn(move || {
+        let mut stdin = std::io::stdin().lock();
+        let mut buf = [0; 64];
+        while let Ok(n @ 1..) = stdin.read(&mut buf) {
+            for key in decode(&buf[..n]) {
+                if tx.send(key).is_err() {
+                    return;
+                }
+            }
+        }
+    });
+    rx
+}
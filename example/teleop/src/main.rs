@@ -0,0 +1,196 @@
+//! Keyboard teleoperation of both arms, hands and neck.
+//!
+//! Holding a key moves at a constant rate; the loop sends and receives at
+//! 50 Hz on its own, so motion stays smooth between key repeats. `g` hands
+//! the arms and hands to the robot's handheld controller through
+//! `JoystickTeleop` and back. Logs go to `teleop.log`, the terminal shows the
+//! help and a live status line.
+
+mod keys;
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::mpsc::TryRecvError;
+use std::time::Instant;
+
+use tokio::time::{Duration, interval};
+use tracing::{Level, info};
+
+use openloong_sdk_rust::app::joystick::JoystickTeleop;
+use openloong_sdk_rust::param::LoongManiParam;
+use openloong_sdk_rust::sdk::ctrl::NeckMode;
+use openloong_sdk_rust::sdk::{LoongManiSdk, arm::Side};
+
+use keys::{Action, HELP, Key, RawTerminal};
+
+const PERIOD: Duration = Duration::from_millis(20);
+/// Rates at speed 1.0: m/s, rad/s, rad/s for the neck, closure %/s.
+const LINEAR_VEL: f32 = 0.05;
+const ANGULAR_VEL: f32 = 0.3;
+const NECK_VEL: f32 = 0.5;
+const FINGER_VEL: f32 = 60.0;
+const SPEEDS: [f32; 2] = [0.1, 4.0];
+/// A key counts as held this long after its last event. Terminals wait
+/// longer before the first repeat than between repeats.
+const FIRST_HOLD: Duration = Duration::from_millis(550);
+const REPEAT_HOLD: Duration = Duration::from_millis(120);
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_ansi(false)
+        .with_writer(std::sync::Mutex::new(std::fs::File::create("teleop.log")?))
+        .init();
+    let param = LoongManiParam::read_from_toml()?;
+    let mut sdk = LoongManiSdk::builder().param(param).build()?;
+
+    // take over from the measured state, not from the startup commands
+    let mut ticker = interval(PERIOD);
+    for _ in 0..100 {
+        sdk.recv()?;
+        if sdk.received() > 0 {
+            break;
+        }
+        ticker.tick().await;
+    }
+    if sdk.received() == 0 {
+        return Err("No SensData from the robot, check target_addr".into());
+    }
+    sdk.engage(sdk.param().engage().clone())?;
+    let mut closure = Side::BOTH.map(|side| sdk.finger_closure(side));
+    let mut neck = match sdk.ctrl().neck_cmd().as_slice() {
+        Some(&[yaw, pitch]) => [yaw, pitch],
+        _ => [0.0; 2],
+    };
+
+    println!("{}", HELP);
+    let _terminal = RawTerminal::enter()?;
+    let keys = keys::spawn_reader();
+    let mut held: HashMap<Action, (Instant, Instant)> = HashMap::new();
+    let mut speed = 1.0_f32;
+    let mut gamepad: Option<JoystickTeleop> = None;
+    let dt = PERIOD.as_secs_f32();
+
+    for frame in 0_u64.. {
+        let now = Instant::now();
+        loop {
+            let key = match keys.try_recv() {
+                Ok(key) => key,
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => Key::CtrlC,
+            };
+            match keys::action(key) {
+                Some(Action::Quit) => {
+                    info!("Teleop stopped");
+                    return Ok(());
+                }
+                Some(Action::Faster) => speed = (speed * 1.25).min(SPEEDS[1]),
+                Some(Action::Slower) => speed = (speed / 1.25).max(SPEEDS[0]),
+                Some(Action::Stop) => held.clear(),
+                Some(Action::Gamepad) => {
+                    gamepad = match gamepad {
+                        Some(_) => None,
+                        None => Some(JoystickTeleop::new(sdk.param().joystick().clone())),
+                    };
+                    info!("Gamepad {}", if gamepad.is_some() { "on" } else { "off" });
+                }
+                Some(action) => {
+                    // (first event, held until)
+                    let entry = held.entry(action).or_insert((now, now));
+                    let hold = if entry.1 > now {
+                        REPEAT_HOLD
+                    } else {
+                        FIRST_HOLD
+                    };
+                    if entry.1 <= now {
+                        entry.0 = now;
+                    }
+                    entry.1 = now + hold;
+                }
+                None => {}
+            }
+        }
+        held.retain(|_, (_, until)| *until > now);
+
+        // moves only apply once the engage blend is over
+        let engaged = sdk.engagement().is_none();
+        for action in held.keys() {
+            match *action {
+                Action::Move { side, axis, dir } if engaged => {
+                    let vel = if axis.is_linear() {
+                        LINEAR_VEL
+                    } else {
+                        ANGULAR_VEL
+                    };
+                    sdk.jog(side, axis, dir as f32 * vel * speed * dt);
+                }
+                Action::Finger { side, dir } => {
+                    let c = &mut closure[side.index()];
+                    *c = (*c + dir as f32 * FINGER_VEL * speed * dt).clamp(0.0, 100.0);
+                }
+                Action::Neck { axis, dir } => {
+                    let limits = sdk.param().neck();
+                    let range = [limits.yaw_limit, limits.pitch_limit][axis];
+                    let q = neck[axis] + dir as f32 * NECK_VEL * speed * dt;
+                    neck[axis] = q.clamp(range[0] as f32, range[1] as f32);
+                }
+                _ => {}
+            }
+        }
+        if engaged && let Some(pad) = &mut gamepad {
+            sdk.joystick(pad, dt as f64)?;
+            // the pad may have opened or closed a hand
+            closure = Side::BOTH.map(|side| sdk.finger_closure(side));
+        }
+        for side in Side::BOTH {
+            sdk.set_finger_closure(side, closure[side.index()]);
+        }
+        if sdk.ctrl().neck_dof() == 2 {
+            sdk.ctrl_mut()
+                .set_neck_mode(NeckMode::JntAxisCtrl)
+                .set_neck_cmd(neck.to_vec().into());
+        }
+
+        sdk.send()?;
+        sdk.recv()?;
+        if frame % 5 == 0 {
+            print_status(&sdk, speed, gamepad.as_ref())?;
+        }
+        ticker.tick().await;
+    }
+    Ok(())
+}
+
+fn print_status(
+    sdk: &LoongManiSdk,
+    speed: f32,
+    gamepad: Option<&JoystickTeleop>,
+) -> std::io::Result<()> {
+    let mut line = String::new();
+    for side in Side::BOTH {
+        let cmd = sdk.ctrl().arm(side).pose();
+        let act = sdk.sens().act_tip_p_rpy2b[side.index()];
+        let p_rpy = |v: [f32; 6]| {
+            format!(
+                "{:+.3} {:+.3} {:+.3} | {:+.2} {:+.2} {:+.2}",
+                v[0], v[1], v[2], v[3], v[4], v[5]
+            )
+        };
+        let [x, y, z] = cmd.xyz;
+        let [roll, pitch, yaw] = cmd.rpy;
+        line += &format!(
+            "{} cmd [{} | elbow {:+.2}] act [{}]  ",
+            side,
+            p_rpy([x, y, z, roll, pitch, yaw]),
+            cmd.elbow_angle,
+            p_rpy(act)
+        );
+    }
+    if let Some(pad) = gamepad {
+        line += &format!("pad {} {:?}  ", pad.side(), pad.mode());
+    }
+    let mut out = std::io::stdout().lock();
+    write!(out, "\r\x1b[2K{}speed x{:.2}", line, speed)?;
+    out.flush()
+}
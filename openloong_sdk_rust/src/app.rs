@@ -1 +1,6 @@
+pub mod bimanual;
+pub mod joystick;
+pub mod look_at;
+pub mod pose_library;
+pub mod posture;
 pub mod preset_movement;
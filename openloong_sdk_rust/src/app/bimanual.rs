@@ -0,0 +1,133 @@
+use tracing::error;
+
+use crate::geometry::Pose;
+use crate::sdk::LoongManiSdk;
+use crate::sdk::arm::Side;
+use crate::sdk::ctrl::{ArmMode, CtrlData};
+
+/// How the two arm commands are tied together.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Coupling {
+    /// The follower is the leader reflected across the body's x-z plane.
+    Mirror { leader: Side },
+    /// The follower keeps `leader_T_follower` to the leader tip.
+    LeaderFollower { leader: Side, offset: Pose },
+    /// Both tips keep `object_T_tip` to a virtual object frame in the body
+    /// frame, e.g. the center of a carried box.
+    Object { object: Pose, grasp: [Pose; 2] },
+}
+
+/// Derives one or both arm commands from a shared reference. Apply with
+/// [`LoongManiSdk::couple`] after moving the leader or the object.
+#[derive(Clone, Debug)]
+pub struct Bimanual {
+    coupling: Coupling,
+}
+
+impl Bimanual {
+    pub fn mirror(leader: Side) -> Self {
+        Self {
+            coupling: Coupling::Mirror { leader },
+        }
+    }
+
+    /// Hold the current relative pose of the commanded tips.
+    pub fn leader_follower(ctrl: &CtrlData, leader: Side) -> Self {
+        let lead = Pose::from_tip(ctrl.arm(leader).pose());
+        let follow = Pose::from_tip(ctrl.arm(leader.other()).pose());
+        Self::with_offset(leader, lead.inverse().compose(&follow))
+    }
+
+    pub fn with_offset(leader: Side, offset: Pose) -> Self {
+        Self {
+            coupling: Coupling::LeaderFollower { leader, offset },
+        }
+    }
+
+    /// Grasp the commanded tips relative to `object`, a body-frame pose.
+    pub fn object(ctrl: &CtrlData, object: Pose) -> Self {
+        let inv = object.inverse();
+        let grasp = Side::BOTH.map(|side| inv.compose(&Pose::from_tip(ctrl.arm(side).pose())));
+        Self {
+            coupling: Coupling::Object { object, grasp },
+        }
+    }
+
+    /// Object frame halfway between the commanded tips, oriented like the
+    /// body, then grasped as in [`Bimanual::object`].
+    pub fn object_between_tips(ctrl: &CtrlData) -> Self {
+        let [l, r] = Side::BOTH.map(|side| ctrl.arm(side).pose().xyz);
+        let center = std::array::from_fn(|i| (l[i] + r[i]) as f64 / 2.0);
+        Self::object(ctrl, Pose::new(center, Pose::IDENTITY.q))
+    }
+
+    pub fn coupling(&self) -> &Coupling {
+        &self.coupling
+    }
+
+    pub fn object_pose(&self) -> Option<&Pose> {
+        match &self.coupling {
+            Coupling::Object { object, .. } => Some(object),
+            _ => None,
+        }
+    }
+
+    /// Move the virtual object; ignored by the other couplings.
+    pub fn set_object(&mut self, pose: Pose) -> &mut Self {
+        if let Coupling::Object { object, .. } = &mut self.coupling {
+            *object = pose;
+        }
+        self
+    }
+
+    /// Shift the object by `delta`, given in the object frame.
+    pub fn move_object(&mut self, delta: &Pose) -> &mut Self {
+        if let Coupling::Object { object, .. } = &mut self.coupling {
+            *object = object.compose(delta);
+        }
+        self
+    }
+
+    /// Rewrite the coupled tip targets in `ctrl`. Elbow angles are kept,
+    /// except that a mirrored follower takes the leader's, and rpy is
+    /// unwrapped toward the current command.
+    pub fn apply(&self, ctrl: &mut CtrlData) {
+        match &self.coupling {
+            Coupling::Mirror { leader } => {
+                let pose = ctrl.arm(*leader).pose().mirrored();
+                ctrl.arm_mut(leader.other()).set_pose(pose);
+            }
+            Coupling::LeaderFollower { leader, offset } => {
+                let lead = Pose::from_tip(ctrl.arm(*leader).pose());
+                let follower = leader.other();
+                let previous = *ctrl.arm(follower).pose();
+                let pose = lead
+                    .compose(offset)
+                    .to_tip_near(previous.elbow_angle, &previous);
+                ctrl.arm_mut(follower).set_pose(pose);
+            }
+            Coupling::Object { object, grasp } => {
+                for side in Side::BOTH {
+                    let previous = *ctrl.arm(side).pose();
+                    let pose = object
+                        .compose(&grasp[side.index()])
+                        .to_tip_near(previous.elbow_angle, &previous);
+                    ctrl.arm_mut(side).set_pose(pose);
+                }
+            }
+        }
+    }
+}
+
+impl LoongManiSdk {
+    /// Apply a bimanual coupling to the arm commands. Needs
+    /// `ArmMode::CartesianBodyFrame`; nothing is sent.
+    pub fn couple(&mut self, bimanual: &Bimanual) -> Result<(), Box<dyn std::error::Error>> {
+        if self.ctrl().arm_mode() != ArmMode::CartesianBodyFrame {
+            error!("Bimanual coupling needs ArmMode::CartesianBodyFrame");
+            return Err("Bimanual coupling needs ArmMode::CartesianBodyFrame".into());
+        }
+        bimanual.apply(self.ctrl_mut());
+        Ok(())
+    }
+}
@@ -0,0 +1,234 @@
+use tracing::{error, info};
+
+use crate::sdk::LoongManiSdk;
+use crate::sdk::arm::{Axis, Side};
+use crate::sdk::ctrl::ArmMode;
+use crate::sdk::hand::GraspPreset;
+
+/// `joy[joy]` drives `axis` of the selected arm; a negative `scale` inverts.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
+pub struct StickBinding {
+    pub joy: usize,
+    pub axis: Axis,
+    #[serde(default = "one")]
+    pub scale: f32,
+}
+
+fn one() -> f32 {
+    1.0
+}
+
+impl StickBinding {
+    pub fn new(joy: usize, axis: Axis, scale: f32) -> Self {
+        Self { joy, axis, scale }
+    }
+}
+
+/// Button bits, counted over `key[0]` (0..16) then `key[1]` (16..32).
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
+#[serde(default)]
+pub struct ButtonMap {
+    pub switch_arm: Option<u8>,
+    pub toggle_hand: Option<u8>,
+    pub switch_mode: Option<u8>,
+}
+
+impl Default for ButtonMap {
+    fn default() -> Self {
+        Self {
+            switch_arm: Some(0),
+            toggle_hand: Some(1),
+            switch_mode: Some(2),
+        }
+    }
+}
+
+/// `[joystick]` table of the param file.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
+#[serde(default)]
+pub struct JoystickConfig {
+    /// Stick deflection ignored around center, fraction of full travel.
+    pub deadzone: f32,
+    /// 0 is linear, 1 is fully cubic.
+    pub expo: f32,
+    /// Velocity at full deflection, m/s and rad/s.
+    pub max_linear: f32,
+    pub max_angular: f32,
+    pub translate: Vec<StickBinding>,
+    pub rotate: Vec<StickBinding>,
+    pub buttons: ButtonMap,
+}
+
+impl Default for JoystickConfig {
+    fn default() -> Self {
+        Self {
+            deadzone: 0.1,
+            expo: 0.3,
+            max_linear: 0.1,
+            max_angular: 0.5,
+            translate: vec![
+                StickBinding::new(0, Axis::Y, -1.0),
+                StickBinding::new(1, Axis::X, 1.0),
+                StickBinding::new(3, Axis::Z, 1.0),
+            ],
+            rotate: vec![
+                StickBinding::new(0, Axis::Yaw, -1.0),
+                StickBinding::new(1, Axis::Pitch, 1.0),
+                StickBinding::new(2, Axis::Roll, 1.0),
+                StickBinding::new(3, Axis::Elbow, 1.0),
+            ],
+            buttons: ButtonMap::default(),
+        }
+    }
+}
+
+impl JoystickConfig {
+    /// Deadzone, then expo; the result keeps the sign and stays in `[-1, 1]`.
+    pub fn shape(&self, v: f32) -> f32 {
+        let a = v.abs().min(1.0);
+        if a <= self.deadzone {
+            return 0.0;
+        }
+        let u = (a - self.deadzone) / (1.0 - self.deadzone);
+        v.signum() * ((1.0 - self.expo) * u + self.expo * u * u * u)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StickMode {
+    Translate,
+    Rotate,
+}
+
+/// Teleop from the handheld controller reported in `SensData::joy` and
+/// `SensData::key`.
+#[derive(Clone, Debug)]
+pub struct JoystickTeleop {
+    config: JoystickConfig,
+    side: Side,
+    mode: StickMode,
+    hand_closed: [bool; 2],
+    keys: Option<[i16; 2]>,
+}
+
+impl JoystickTeleop {
+    pub fn new(config: JoystickConfig) -> Self {
+        Self {
+            config,
+            side: Side::Left,
+            mode: StickMode::Translate,
+            hand_closed: [false; 2],
+            keys: None,
+        }
+    }
+
+    pub fn config(&self) -> &JoystickConfig {
+        &self.config
+    }
+
+    pub fn set_config(&mut self, config: JoystickConfig) {
+        self.config = config;
+    }
+
+    pub fn side(&self) -> Side {
+        self.side
+    }
+
+    pub fn mode(&self) -> StickMode {
+        self.mode
+    }
+
+    /// Velocity per [`Axis`] for the current mode, m/s and rad/s.
+    pub fn velocity(&self, joy: &[f32; 4]) -> [f32; 7] {
+        let bindings = match self.mode {
+            StickMode::Translate => &self.config.translate,
+            StickMode::Rotate => &self.config.rotate,
+        };
+        let mut vel = [0.0; 7];
+        for b in bindings {
+            let Some(&v) = joy.get(b.joy) else {
+                continue;
+            };
+            let max = if b.axis.is_linear() {
+                self.config.max_linear
+            } else {
+                self.config.max_angular
+            };
+            vel[b.axis.index()] += b.scale * self.config.shape(v) * max;
+        }
+        vel
+    }
+
+    /// `[switch_arm, toggle_hand, switch_mode]` pressed since the last
+    /// call; nothing on the first call, so a button held at startup does not
+    /// fire.
+    fn pressed(&mut self, keys: [i16; 2]) -> [bool; 3] {
+        let last = self.keys.replace(keys).unwrap_or(keys);
+        let edge = |bit: Option<u8>| {
+            let Some(bit) = bit.filter(|&b| b < 32) else {
+                return false;
+            };
+            let (word, mask) = ((bit / 16) as usize, 1u16 << (bit % 16));
+            keys[word] as u16 & mask != 0 && last[word] as u16 & mask == 0
+        };
+        let buttons = &self.config.buttons;
+        [
+            edge(buttons.switch_arm),
+            edge(buttons.toggle_hand),
+            edge(buttons.switch_mode),
+        ]
+    }
+}
+
+impl LoongManiSdk {
+    /// Apply the latest `joy` and `key` fields: buttons switch arm, hand and
+    /// stick mode, the sticks jog the selected arm for `dt` seconds in the
+    /// `[preset]` frame. `teleop` takes the current `[joystick]` table first,
+    /// so a param reload reaches it.
+    /// Needs `ArmMode::CartesianBodyFrame`; nothing is sent.
+    pub fn joystick(
+        &mut self,
+        teleop: &mut JoystickTeleop,
+        dt: f64,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if self.ctrl().arm_mode() != ArmMode::CartesianBodyFrame {
+            error!("joystick needs ArmMode::CartesianBodyFrame");
+            return Err("joystick needs ArmMode::CartesianBodyFrame".into());
+        }
+        if teleop.config() != self.param().joystick() {
+            teleop.set_config(self.param().joystick().clone());
+        }
+        let (joy, keys) = (self.sens().joy, self.sens().key);
+        let [switch_arm, toggle_hand, switch_mode] = teleop.pressed(keys);
+        if switch_arm {
+            teleop.side = teleop.side.other();
+            info!("Joystick drives the {} arm", teleop.side);
+        }
+        if switch_mode {
+            teleop.mode = match teleop.mode {
+                StickMode::Translate => StickMode::Rotate,
+                StickMode::Rotate => StickMode::Translate,
+            };
+            info!("Joystick mode {:?}", teleop.mode);
+        }
+        if toggle_hand {
+            let closed = &mut teleop.hand_closed[teleop.side.index()];
+            *closed = !*closed;
+            let preset = if *closed {
+                GraspPreset::Close
+            } else {
+                GraspPreset::Open
+            };
+            self.set_grasp(teleop.side, preset);
+        }
+
+        let vel = teleop.velocity(&joy);
+        for axis in Axis::ALL {
+            let v = vel[axis.index()];
+            if v != 0.0 {
+                self.jog(teleop.side, axis, v * dt as f32);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,148 @@
+use ndarray::Array1;
+use tracing::error;
+
+use crate::sdk::LoongManiSdk;
+use crate::sdk::arm::Side;
+use crate::sdk::ctrl::NeckMode;
+
+/// Two-joint neck: yaw about body z, then pitch about the rotated y axis.
+/// Positive pitch tilts the head down.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
+#[serde(default)]
+pub struct NeckKinematics {
+    /// Intersection of the yaw and pitch axes in the body frame, meters.
+    pub pivot: [f64; 3],
+    /// Height of the eyes/camera above the pitch axis, meters.
+    pub eye_height: f64,
+    pub yaw_limit: [f64; 2],
+    pub pitch_limit: [f64; 2],
+    /// Joint speed limits `[yaw, pitch]` for gaze tracking, rad/s.
+    pub max_vel: [f64; 2],
+}
+
+impl Default for NeckKinematics {
+    fn default() -> Self {
+        Self {
+            pivot: [0.0, 0.0, 0.45],
+            eye_height: 0.1,
+            yaw_limit: [-1.2, 1.2],
+            pitch_limit: [-0.5, 0.9],
+            max_vel: [1.5, 1.5],
+        }
+    }
+}
+
+impl NeckKinematics {
+    /// `[yaw, pitch]` pointing the eyes at `target`, clamped to joint limits.
+    pub fn look_at(&self, target: [f64; 3]) -> [f64; 2] {
+        let d = [
+            target[0] - self.pivot[0],
+            target[1] - self.pivot[1],
+            target[2] - self.pivot[2],
+        ];
+        let yaw = d[1].atan2(d[0]);
+        let r = (d[0] * d[0] + d[1] * d[1]).sqrt();
+        let dist = (r * r + d[2] * d[2]).sqrt();
+        // angle down to the target, corrected for the eyes sitting above the axis
+        let mut pitch = (-d[2]).atan2(r);
+        if dist > self.eye_height {
+            pitch += (self.eye_height / dist).asin();
+        }
+        self.clamp([yaw, pitch])
+    }
+
+    pub fn clamp(&self, q: [f64; 2]) -> [f64; 2] {
+        [
+            q[0].clamp(self.yaw_limit[0], self.yaw_limit[1]),
+            q[1].clamp(self.pitch_limit[0], self.pitch_limit[1]),
+        ]
+    }
+}
+
+/// Rate-limited gaze that follows a moving target.
+#[derive(Clone, Debug)]
+pub struct GazeTracker {
+    kinematics: NeckKinematics,
+    current: Option<[f64; 2]>,
+}
+
+impl GazeTracker {
+    pub fn new(kinematics: NeckKinematics) -> Self {
+        Self {
+            kinematics,
+            current: None,
+        }
+    }
+
+    pub fn kinematics(&self) -> &NeckKinematics {
+        &self.kinematics
+    }
+
+    pub fn current(&self) -> Option<[f64; 2]> {
+        self.current
+    }
+
+    /// Replace the geometry and limits, keeping the current angles within them.
+    pub fn set_config(&mut self, kinematics: NeckKinematics) {
+        self.current = self.current.map(|q| kinematics.clamp(q));
+        self.kinematics = kinematics;
+    }
+
+    /// Start tracking from the given joint angles, e.g. the measured neck.
+    pub fn reset(&mut self, q: [f64; 2]) {
+        self.current = Some(self.kinematics.clamp(q));
+    }
+
+    pub fn step(&mut self, target: [f64; 3], dt: f64) -> [f64; 2] {
+        let goal = self.kinematics.look_at(target);
+        let next = match self.current {
+            Some(cur) => {
+                let mut next = cur;
+                for i in 0..2 {
+                    let max_step = self.kinematics.max_vel[i] * dt;
+                    next[i] += (goal[i] - cur[i]).clamp(-max_step, max_step);
+                }
+                next
+            }
+            None => goal,
+        };
+        self.current = Some(next);
+        next
+    }
+}
+
+impl LoongManiSdk {
+    /// Advance `tracker` towards a body-frame point and write the result to
+    /// `neck_cmd` in `NeckMode::JntAxisCtrl`. The tracker takes `[neck]` from
+    /// [`LoongManiSdk::param`], so a param reload applies from this step on.
+    pub fn look_at(
+        &mut self,
+        tracker: &mut GazeTracker,
+        target: [f64; 3],
+        dt: f64,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if self.ctrl().neck_cmd().len() != 2 {
+            error!("look_at needs a 2 dof neck");
+            return Err("look_at needs a 2 dof neck".into());
+        }
+        if tracker.kinematics() != self.param().neck() {
+            tracker.set_config(self.param().neck().clone());
+        }
+        let q = tracker.step(target, dt);
+        self.ctrl_mut()
+            .set_neck_mode(NeckMode::JntAxisCtrl)
+            .set_neck_cmd(Array1::from_iter(q.map(|v| v as f32)));
+        Ok(())
+    }
+
+    /// Look at the measured tip of one hand.
+    pub fn look_at_tip(
+        &mut self,
+        tracker: &mut GazeTracker,
+        side: Side,
+        dt: f64,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let tip = self.sens().act_tip_p_rpy2b[side.index()];
+        self.look_at(tracker, [tip[0] as f64, tip[1] as f64, tip[2] as f64], dt)
+    }
+}
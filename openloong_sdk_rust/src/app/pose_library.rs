@@ -0,0 +1,341 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use ndarray::Array1;
+use tracing::{error, info};
+
+use crate::geometry::Pose;
+use crate::sdk::LoongManiSdk;
+use crate::sdk::arm::{Side, TipPose};
+use crate::sdk::ctrl::{ArmMode, CtrlData};
+
+/// Commands for the whole upper body. Empty finger, neck or lumbar lists
+/// leave that part alone when the pose is played back.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct NamedPose {
+    /// `[x, y, z, roll, pitch, yaw, elbow_angle]` per arm.
+    pub left: [f32; 7],
+    pub right: [f32; 7],
+    pub finger_left: Vec<f32>,
+    pub finger_right: Vec<f32>,
+    pub neck: Vec<f32>,
+    pub lumbar: Vec<f32>,
+}
+
+impl NamedPose {
+    pub fn from_ctrl(ctrl: &CtrlData) -> Self {
+        Self {
+            left: ctrl.arm(Side::Left).pose().to_array(),
+            right: ctrl.arm(Side::Right).pose().to_array(),
+            finger_left: ctrl.finger(Side::Left).to_vec(),
+            finger_right: ctrl.finger(Side::Right).to_vec(),
+            neck: ctrl.neck_cmd().to_vec(),
+            lumbar: ctrl.lumbar_cmd().to_vec(),
+        }
+    }
+
+    pub fn arm(&self, side: Side) -> TipPose {
+        match side {
+            Side::Left => TipPose::from(self.left),
+            Side::Right => TipPose::from(self.right),
+        }
+    }
+
+    pub fn set_arm(&mut self, side: Side, pose: TipPose) {
+        match side {
+            Side::Left => self.left = pose.to_array(),
+            Side::Right => self.right = pose.to_array(),
+        }
+    }
+
+    pub fn finger(&self, side: Side) -> &[f32] {
+        match side {
+            Side::Left => &self.finger_left,
+            Side::Right => &self.finger_right,
+        }
+    }
+
+    /// The whole pose reflected across the body's x-z plane: arms and hands
+    /// swap sides, neck yaw and lumbar roll/yaw change sign.
+    pub fn mirrored(&self) -> Self {
+        let mut neck = self.neck.clone();
+        if let Some(yaw) = neck.first_mut() {
+            *yaw = -*yaw;
+        }
+        let mut lumbar = self.lumbar.clone();
+        for i in [0, 2] {
+            if let Some(v) = lumbar.get_mut(i) {
+                *v = -*v;
+            }
+        }
+        Self {
+            left: self.arm(Side::Right).mirrored().to_array(),
+            right: self.arm(Side::Left).mirrored().to_array(),
+            finger_left: self.finger_right.clone(),
+            finger_right: self.finger_left.clone(),
+            neck,
+            lumbar,
+        }
+    }
+
+    /// Copy the `from` arm and hand onto the other side, mirrored.
+    pub fn symmetric(&self, from: Side) -> Self {
+        let mut pose = self.clone();
+        pose.set_arm(from.other(), self.arm(from).mirrored());
+        let finger = self.finger(from).to_vec();
+        match from {
+            Side::Left => pose.finger_right = finger,
+            Side::Right => pose.finger_left = finger,
+        }
+        pose
+    }
+}
+
+/// Named poses kept in a TOML file, one table per name.
+#[derive(Clone, Debug, Default)]
+pub struct PoseLibrary {
+    path: Option<PathBuf>,
+    poses: BTreeMap<String, NamedPose>,
+}
+
+impl PoseLibrary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open a library file; a missing file gives an empty library that
+    /// [`PoseLibrary::save`] will create.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        let path = path.as_ref();
+        let poses = match std::fs::read_to_string(path) {
+            Ok(text) => toml::from_str(&text)
+                .map_err(|e| format!("Invalid pose library {}: {}", path.display(), e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e).into()),
+        };
+        Ok(Self {
+            path: Some(path.to_path_buf()),
+            poses,
+        })
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Write back to the file the library was opened from.
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let path = self.path.as_ref().ok_or("Pose library has no file")?;
+        self.save_to(path)
+    }
+
+    pub fn save_to(&self, path: impl AsRef<Path>) -> Result<(), Box<dyn std::error::Error>> {
+        std::fs::write(path.as_ref(), toml::to_string(&self.poses)?)?;
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&NamedPose> {
+        self.poses.get(name)
+    }
+
+    /// Add or replace a pose.
+    pub fn insert(&mut self, name: &str, pose: NamedPose) -> Option<NamedPose> {
+        self.poses.insert(name.to_string(), pose)
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<NamedPose> {
+        self.poses.remove(name)
+    }
+
+    /// Pose names in sorted order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.poses.keys().map(String::as_str)
+    }
+}
+
+/// Smooth move of the commands from where they are to a [`NamedPose`].
+#[derive(Clone, Debug)]
+pub struct PoseMotion {
+    start: NamedPose,
+    target: NamedPose,
+    /// Last commands handed out, to keep rpy continuous.
+    previous: NamedPose,
+    duration: f64,
+    elapsed: f64,
+}
+
+impl PoseMotion {
+    pub fn new(start: NamedPose, target: NamedPose, duration: f64) -> Self {
+        Self {
+            previous: start.clone(),
+            start,
+            target,
+            duration,
+            elapsed: 0.0,
+        }
+    }
+
+    pub fn target(&self) -> &NamedPose {
+        &self.target
+    }
+
+    /// Fraction of the motion completed, in `[0, 1]`.
+    pub fn progress(&self) -> f64 {
+        if self.duration <= 0.0 {
+            return 1.0;
+        }
+        (self.elapsed / self.duration).min(1.0)
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.progress() >= 1.0
+    }
+
+    /// Commands `dt` seconds further along.
+    pub fn step(&mut self, dt: f64) -> NamedPose {
+        self.elapsed += dt;
+        let t = self.progress();
+        if t >= 1.0 {
+            self.previous = self.target.clone();
+            return self.target.clone();
+        }
+        // smoothstep, so the motion starts and ends at rest
+        let s = t * t * (3.0 - 2.0 * t);
+        let mut pose = self.target.clone();
+        for side in Side::BOTH {
+            let (a, b) = (self.start.arm(side), self.target.arm(side));
+            let p = Pose::from_tip(&a).interpolate(&Pose::from_tip(&b), s);
+            let elbow = a.elbow_angle + (b.elbow_angle - a.elbow_angle) * s as f32;
+            pose.set_arm(side, p.to_tip_near(elbow, &self.previous.arm(side)));
+        }
+        let lerp = |a: &[f32], b: &[f32]| -> Vec<f32> {
+            if a.len() != b.len() {
+                return b.to_vec();
+            }
+            a.iter()
+                .zip(b)
+                .map(|(a, b)| a + (b - a) * s as f32)
+                .collect()
+        };
+        pose.finger_left = lerp(&self.start.finger_left, &self.target.finger_left);
+        pose.finger_right = lerp(&self.start.finger_right, &self.target.finger_right);
+        pose.neck = lerp(&self.start.neck, &self.target.neck);
+        pose.lumbar = lerp(&self.start.lumbar, &self.target.lumbar);
+        self.previous = pose.clone();
+        pose
+    }
+}
+
+impl LoongManiSdk {
+    /// Measured tip poses, fingers, neck and lumbar from the latest
+    /// `SensData`. The elbow angles, which are not measured, come from the
+    /// current commands.
+    pub fn capture_pose(&self) -> Result<NamedPose, Box<dyn std::error::Error>> {
+        let mut ctrl = self.ctrl().clone();
+        let sens = self.sens();
+        let seeded = [
+            ctrl.seed_arm(sens, Side::Left, ArmMode::CartesianBodyFrame),
+            ctrl.seed_arm(sens, Side::Right, ArmMode::CartesianBodyFrame),
+            ctrl.seed_fingers(sens),
+            ctrl.seed_neck(sens),
+            ctrl.seed_lumbar(sens),
+        ];
+        if seeded.contains(&false) {
+            error!("SensData does not hold a full pose");
+            return Err("SensData does not hold a full pose".into());
+        }
+        Ok(NamedPose::from_ctrl(&ctrl))
+    }
+
+    /// Capture the current pose under `name` and write the library file.
+    pub fn save_pose(
+        &self,
+        library: &mut PoseLibrary,
+        name: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        library.insert(name, self.capture_pose()?);
+        library.save()?;
+        info!("Saved pose {}", name);
+        Ok(())
+    }
+
+    /// Start a smooth move from the current commands to a library pose.
+    /// Step the returned motion with [`LoongManiSdk::play`].
+    pub fn go_to_named(
+        &self,
+        library: &PoseLibrary,
+        name: &str,
+        duration: f64,
+    ) -> Result<PoseMotion, Box<dyn std::error::Error>> {
+        let Some(target) = library.get(name) else {
+            error!("No pose named {}", name);
+            return Err(format!("No pose named {}", name).into());
+        };
+        self.go_to(target.clone(), duration)
+    }
+
+    pub fn go_to(
+        &self,
+        target: NamedPose,
+        duration: f64,
+    ) -> Result<PoseMotion, Box<dyn std::error::Error>> {
+        let ctrl = self.ctrl();
+        let parts = [
+            (
+                "finger_left",
+                target.finger_left.len(),
+                ctrl.finger(Side::Left).len(),
+            ),
+            (
+                "finger_right",
+                target.finger_right.len(),
+                ctrl.finger(Side::Right).len(),
+            ),
+            ("neck", target.neck.len(), ctrl.neck_cmd().len()),
+            ("lumbar", target.lumbar.len(), ctrl.lumbar_cmd().len()),
+        ];
+        for (name, len, dof) in parts {
+            if len != 0 && len != dof {
+                error!("Pose {} has {} values, expected {}", name, len, dof);
+                return Err(format!("Pose {} has {} values, expected {}", name, len, dof).into());
+            }
+        }
+        Ok(PoseMotion::new(
+            NamedPose::from_ctrl(ctrl),
+            target,
+            duration,
+        ))
+    }
+
+    /// Advance `motion` by `dt` and write the commands; modes are left as
+    /// they are, so the arms need `ArmMode::CartesianBodyFrame`. Nothing is
+    /// sent.
+    pub fn play(
+        &mut self,
+        motion: &mut PoseMotion,
+        dt: f64,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if self.ctrl().arm_mode() != ArmMode::CartesianBodyFrame {
+            error!("Pose playback needs ArmMode::CartesianBodyFrame");
+            return Err("Pose playback needs ArmMode::CartesianBodyFrame".into());
+        }
+        let pose = motion.step(dt);
+        let ctrl = self.ctrl_mut();
+        for side in Side::BOTH {
+            ctrl.arm_mut(side).set_pose(pose.arm(side));
+        }
+        for side in Side::BOTH {
+            if !pose.finger(side).is_empty() {
+                ctrl.set_finger(side, Array1::from_vec(pose.finger(side).to_vec()));
+            }
+        }
+        if !pose.neck.is_empty() {
+            ctrl.set_neck_cmd(Array1::from_vec(pose.neck));
+        }
+        if !pose.lumbar.is_empty() {
+            ctrl.set_lumbar_cmd(Array1::from_vec(pose.lumbar));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,213 @@
+use ndarray::Array1;
+use tracing::error;
+
+use crate::geometry::{Pose, Quat};
+use crate::sdk::LoongManiSdk;
+use crate::sdk::arm::Side;
+use crate::sdk::ctrl::{ArmMode, LumbarMode};
+
+/// Torso orientation relative to upright, radians. Sent in `lumbar_cmd` as
+/// `[roll, pitch, yaw]` under `LumbarMode::PostCtrl`.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct TorsoPosture {
+    pub roll: f64,
+    pub pitch: f64,
+    pub yaw: f64,
+}
+
+impl TorsoPosture {
+    pub fn to_array(&self) -> [f64; 3] {
+        [self.roll, self.pitch, self.yaw]
+    }
+
+    pub fn from_array(v: [f64; 3]) -> Self {
+        Self {
+            roll: v[0],
+            pitch: v[1],
+            yaw: v[2],
+        }
+    }
+}
+
+/// Geometry and limits of the torso as seen from the upright body frame.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
+#[serde(default)]
+pub struct LumbarConfig {
+    /// Point the torso rotates about, body frame, meters.
+    pub pivot: [f64; 3],
+    /// Shoulder centers `[left, right]`, body frame, meters.
+    pub shoulder: [[f64; 3]; 2],
+    /// Comfortable reach radius around each shoulder, meters.
+    pub reach: f64,
+    /// `[roll, pitch, yaw]` lower and upper limits, radians.
+    pub min: [f64; 3],
+    pub max: [f64; 3],
+    /// `[roll, pitch, yaw]` rate limits, rad/s.
+    pub max_vel: [f64; 3],
+}
+
+impl Default for LumbarConfig {
+    fn default() -> Self {
+        Self {
+            pivot: [0.0, 0.0, -0.35],
+            shoulder: [[0.0, 0.2, 0.1], [0.0, -0.2, 0.1]],
+            reach: 0.6,
+            min: [-0.2, -0.1, -0.5],
+            max: [0.2, 0.5, 0.5],
+            max_vel: [0.3, 0.3, 0.5],
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PostureController {
+    config: LumbarConfig,
+    current: TorsoPosture,
+}
+
+impl PostureController {
+    pub fn new(config: LumbarConfig) -> Self {
+        Self {
+            config,
+            current: TorsoPosture::default(),
+        }
+    }
+
+    pub fn config(&self) -> &LumbarConfig {
+        &self.config
+    }
+
+    pub fn current(&self) -> TorsoPosture {
+        self.current
+    }
+
+    /// Replace the geometry and limits, keeping the current posture within them.
+    pub fn set_config(&mut self, config: LumbarConfig) {
+        self.config = config;
+        self.current = self.clamp(self.current);
+    }
+
+    pub fn reset(&mut self, posture: TorsoPosture) {
+        self.current = self.clamp(posture);
+    }
+
+    pub fn clamp(&self, posture: TorsoPosture) -> TorsoPosture {
+        let v = posture.to_array();
+        TorsoPosture::from_array(std::array::from_fn(|i| {
+            v[i].clamp(self.config.min[i], self.config.max[i])
+        }))
+    }
+
+    /// Move towards `goal` within the rate limits.
+    pub fn step(&mut self, goal: TorsoPosture, dt: f64) -> TorsoPosture {
+        let goal = self.clamp(goal).to_array();
+        let mut cur = self.current.to_array();
+        for i in 0..3 {
+            let max_step = self.config.max_vel[i] * dt;
+            cur[i] += (goal[i] - cur[i]).clamp(-max_step, max_step);
+        }
+        self.current = TorsoPosture::from_array(cur);
+        self.current
+    }
+
+    /// Posture that brings the shoulder close enough to reach `target`, given
+    /// in the upright body frame. Zero when the target is already in reach.
+    pub fn lean_toward(&self, side: Side, target: [f64; 3]) -> TorsoPosture {
+        let s = self.config.shoulder[side.index()];
+        let d = [target[0] - s[0], target[1] - s[1], target[2] - s[2]];
+        let dist = (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).sqrt();
+        if dist <= self.config.reach {
+            return TorsoPosture::default();
+        }
+        let excess = 1.0 - self.config.reach / dist;
+        let lever = s[2] - self.config.pivot[2];
+        if lever <= f64::EPSILON {
+            return TorsoPosture::default();
+        }
+        // pitching forward moves the shoulder along +x, rolling negative along +y
+        self.clamp(TorsoPosture {
+            roll: -(d[1] * excess / lever).clamp(-1.0, 1.0).asin(),
+            pitch: (d[0] * excess / lever).clamp(-1.0, 1.0).asin(),
+            yaw: 0.0,
+        })
+    }
+
+    /// `upright_T_torso` for the current posture, rotating about the pivot.
+    pub fn torso_pose(&self) -> Pose {
+        let q = Quat::from_rpy(self.current.to_array());
+        let r = q.rotate(self.config.pivot);
+        let p = self.config.pivot;
+        Pose::new([p[0] - r[0], p[1] - r[1], p[2] - r[2]], q)
+    }
+
+    /// Re-express an upright-frame target in the leaned torso frame.
+    pub fn to_torso(&self, upright_target: &Pose) -> Pose {
+        self.torso_pose().inverse().compose(upright_target)
+    }
+}
+
+fn check_arm_mode(arm_mode: ArmMode) -> Result<(), Box<dyn std::error::Error>> {
+    match arm_mode {
+        ArmMode::Reset | ArmMode::LowerLimbCmdPassthrough => {
+            error!("Posture control unavailable in {:?}", arm_mode);
+            Err(format!("Posture control unavailable in {:?}", arm_mode).into())
+        }
+        _ => Ok(()),
+    }
+}
+
+impl LoongManiSdk {
+    /// Rate-limited move of the torso towards `goal` in `LumbarMode::PostCtrl`.
+    /// The controller takes `[lumbar]` from [`LoongManiSdk::param`], so a
+    /// param reload applies from this step on.
+    pub fn set_posture(
+        &mut self,
+        controller: &mut PostureController,
+        goal: TorsoPosture,
+        dt: f64,
+    ) -> Result<TorsoPosture, Box<dyn std::error::Error>> {
+        check_arm_mode(self.ctrl().arm_mode())?;
+        if self.ctrl().lumbar_cmd().len() != 3 {
+            error!("Posture control needs a 3 dof lumbar");
+            return Err("Posture control needs a 3 dof lumbar".into());
+        }
+        self.sync_lumbar(controller);
+        let posture = controller.step(goal, dt);
+        self.ctrl_mut()
+            .set_lumbar_mode(LumbarMode::PostCtrl)
+            .set_lumbar_cmd(Array1::from_iter(posture.to_array().map(|v| v as f32)));
+        Ok(posture)
+    }
+
+    fn sync_lumbar(&self, controller: &mut PostureController) {
+        if controller.config() != self.param().lumbar() {
+            controller.set_config(self.param().lumbar().clone());
+        }
+    }
+
+    /// Command one arm to an upright-frame target, leaning the torso when the
+    /// target is outside the reach envelope. The arm command is compensated
+    /// for the current lean, so it needs `ArmMode::CartesianBodyFrame`.
+    pub fn reach(
+        &mut self,
+        controller: &mut PostureController,
+        side: Side,
+        target: &Pose,
+        elbow_angle: f32,
+        dt: f64,
+    ) -> Result<TorsoPosture, Box<dyn std::error::Error>> {
+        if self.ctrl().arm_mode() != ArmMode::CartesianBodyFrame {
+            error!("reach needs ArmMode::CartesianBodyFrame");
+            return Err("reach needs ArmMode::CartesianBodyFrame".into());
+        }
+        self.sync_lumbar(controller);
+        let goal = controller.lean_toward(side, target.p);
+        let posture = self.set_posture(controller, goal, dt)?;
+        let previous = *self.ctrl().arm(side).pose();
+        let cmd = controller
+            .to_torso(target)
+            .to_tip_near(elbow_angle, &previous);
+        self.ctrl_mut().arm_mut(side).set_pose(cmd);
+        Ok(posture)
+    }
+}
@@ -1,164 +1,135 @@
-use ndarray::array;
+use ndarray::Array1;
 use tracing::error;
 
+use crate::geometry::{Pose, Quat};
 use crate::sdk::LoongManiSdk;
+use crate::sdk::arm::{Axis, Side};
 
-impl LoongManiSdk {
-    const MOVE_INCREMENT: f64 = 0.05;
+/// Frame a jog is expressed in. Both rotate about the tip point.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JogFrame {
+    /// Along and about the body axes.
+    #[default]
+    Body,
+    /// Along and about the current tip axes.
+    Tool,
+}
 
-    pub fn up(&mut self, arm: &str) -> Result<(), Box<dyn std::error::Error>> {
-        let mut arm_data = self.ctrl().arm_cmd();
-        match arm {
-            "left" => {
-                arm_data[[0, 2]] += Self::MOVE_INCREMENT as f32;
-            }
-            "right" => {
-                arm_data[[1, 2]] += Self::MOVE_INCREMENT as f32;
-            }
-            _ => return Err("Invalid arm string".into()),
-        }
-        println!("arm_data: {:?}", arm_data);
-        self.ctrl_mut().set_arm_cmd(arm_data);
-        Ok(())
-    }
+/// Size of one jog step per axis, meters and radians.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
+#[serde(default)]
+pub struct JogStep {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub roll: f32,
+    pub pitch: f32,
+    pub yaw: f32,
+    pub elbow: f32,
+}
 
-    pub fn down(&mut self, arm: &str) -> Result<(), Box<dyn std::error::Error>> {
-        let mut arm_data = self.ctrl().arm_cmd();
-        match arm {
-            "left" => {
-                arm_data[[0, 2]] -= Self::MOVE_INCREMENT as f32;
-            }
-            "right" => {
-                arm_data[[1, 2]] -= Self::MOVE_INCREMENT as f32;
-            }
-            _ => return Err("Invalid arm string".into()),
+impl Default for JogStep {
+    fn default() -> Self {
+        Self {
+            x: 0.05,
+            y: 0.05,
+            z: 0.05,
+            roll: 0.1,
+            pitch: 0.1,
+            yaw: 0.1,
+            elbow: 0.1,
         }
-        println!("arm_data: {:?}", arm_data);
-        self.ctrl_mut().set_arm_cmd(arm_data);
-        Ok(())
     }
+}
 
-    pub fn w(&mut self, arm: &str) -> Result<(), Box<dyn std::error::Error>> {
-        let mut arm_data = self.ctrl().arm_cmd();
-        match arm {
-            "left" => {
-                arm_data[[0, 0]] += Self::MOVE_INCREMENT as f32;
-            }
-            "right" => {
-                arm_data[[1, 0]] += Self::MOVE_INCREMENT as f32;
-            }
-            _ => return Err("Invalid arm string".into()),
+impl JogStep {
+    pub fn get(&self, axis: Axis) -> f32 {
+        match axis {
+            Axis::X => self.x,
+            Axis::Y => self.y,
+            Axis::Z => self.z,
+            Axis::Roll => self.roll,
+            Axis::Pitch => self.pitch,
+            Axis::Yaw => self.yaw,
+            Axis::Elbow => self.elbow,
         }
-        println!("arm_data: {:?}", arm_data);
-        self.ctrl_mut().set_arm_cmd(arm_data);
-        Ok(())
     }
+}
 
-    pub fn s(&mut self, arm: &str) -> Result<(), Box<dyn std::error::Error>> {
-        let mut arm_data = self.ctrl().arm_cmd();
-        match arm {
-            "left" => {
-                arm_data[[0, 0]] -= Self::MOVE_INCREMENT as f32;
-            }
-            "right" => {
-                arm_data[[1, 0]] -= Self::MOVE_INCREMENT as f32;
-            }
-            _ => return Err("Invalid arm string".into()),
-        }
-        println!("arm_data: {:?}", arm_data);
-        self.ctrl_mut().set_arm_cmd(arm_data);
-        Ok(())
-    }
+/// `[preset]` table of the param file.
+#[derive(Clone, Debug, Default, PartialEq, serde::Deserialize)]
+#[serde(default)]
+pub struct PresetConfig {
+    pub step: JogStep,
+    pub frame: JogFrame,
+}
 
-    pub fn a(&mut self, arm: &str) -> Result<(), Box<dyn std::error::Error>> {
-        let mut arm_data = self.ctrl().arm_cmd();
-        match arm {
-            "left" => {
-                arm_data[[0, 1]] += Self::MOVE_INCREMENT as f32;
-            }
-            "right" => {
-                arm_data[[1, 1]] += Self::MOVE_INCREMENT as f32;
-            }
-            _ => return Err("Invalid arm string".into()),
-        }
-        println!("arm_data: {:?}", arm_data);
-        self.ctrl_mut().set_arm_cmd(arm_data);
-        Ok(())
+// jogging edits the commanded tip pose only, `send` transmits it
+impl LoongManiSdk {
+    /// Move `axis` of one arm by `delta` in the frame from `[preset]`.
+    pub fn jog(&mut self, side: Side, axis: Axis, delta: f32) -> &mut Self {
+        let frame = self.param().preset().frame;
+        self.jog_in(side, axis, delta, frame)
     }
 
-    pub fn d(&mut self, arm: &str) -> Result<(), Box<dyn std::error::Error>> {
-        let mut arm_data = self.ctrl().arm_cmd();
-        match arm {
-            "left" => {
-                arm_data[[0, 1]] -= Self::MOVE_INCREMENT as f32;
-            }
-            "right" => {
-                arm_data[[1, 1]] -= Self::MOVE_INCREMENT as f32;
-            }
-            _ => return Err("Invalid arm string".into()),
-        }
-        println!("arm_data: {:?}", arm_data);
-        self.ctrl_mut().set_arm_cmd(arm_data);
-        Ok(())
+    /// Move by `steps` configured steps, e.g. `1.0` or `-1.0` per key press.
+    pub fn jog_step(&mut self, side: Side, axis: Axis, steps: f32) -> &mut Self {
+        let delta = steps * self.param().preset().step.get(axis);
+        self.jog(side, axis, delta)
     }
 
-    pub fn handle_xyzrpy(
-        &mut self,
-        arm_string: &str,
-        xyzrpy: Vec<f64>,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        if xyzrpy.len() != 6 {
-            return Err("Invalid data length".into());
+    pub fn jog_in(&mut self, side: Side, axis: Axis, delta: f32, frame: JogFrame) -> &mut Self {
+        let pose = self.ctrl_mut().arm_mut(side).pose_mut();
+        if axis == Axis::Elbow {
+            pose.elbow_angle += delta;
+            return self;
         }
-        let mut arm_cmd_data = self.ctrl().arm_cmd().clone();
-        let row = match arm_string {
-            "left" => 0_usize,
-            "right" => 1_usize,
-            _ => {
-                error!("Invalid arm string");
-                return Err("Invalid arm string".into());
-            }
-        };
-        for (i, item) in xyzrpy.iter().enumerate() {
-            arm_cmd_data[[row, i]] = *item as f32
+        let delta = delta as f64;
+        let mut unit = [0.0; 3];
+        unit[axis.index() % 3] = 1.0;
+        let tip = Pose::from_tip(pose);
+        if axis.is_linear() {
+            // rpy is left as commanded, a round trip could flip roll by 2pi
+            let d = match frame {
+                JogFrame::Body => unit,
+                JogFrame::Tool => tip.q.rotate(unit),
+            };
+            for (v, d) in pose.xyz.iter_mut().zip(d) {
+                *v += (d * delta) as f32;
+            }
+            return self;
         }
-        self.ctrl_mut().set_arm_cmd(arm_cmd_data);
-        Ok(())
+        let q = match frame {
+            JogFrame::Body => Quat::from_axis_angle(unit, delta) * tip.q,
+            JogFrame::Tool => tip.q * Quat::from_axis_angle(unit, delta),
+        };
+        let previous = *pose;
+        *pose = Pose::new(tip.p, q).to_tip_near(previous.elbow_angle, &previous);
+        self
+    }
+
+    /// Replace the tip position and orientation `[x, y, z, roll, pitch, yaw]`
+    /// of one arm, keeping its elbow angle.
+    pub fn set_tip(&mut self, side: Side, p_rpy: [f32; 6]) -> &mut Self {
+        let pose = self.ctrl_mut().arm_mut(side).pose_mut();
+        pose.xyz = [p_rpy[0], p_rpy[1], p_rpy[2]];
+        pose.rpy = [p_rpy[3], p_rpy[4], p_rpy[5]];
+        self
     }
 
-    pub fn handle_finger(
+    /// Set the finger command of one hand, checking its length.
+    pub fn set_finger_cmd(
         &mut self,
-        arm_string: &str,
-        finger: &[f64],
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        if finger.len() != self.ctrl().finger_dof().unwrap() as usize {
+        side: Side,
+        finger: &[f32],
+    ) -> Result<&mut Self, Box<dyn std::error::Error>> {
+        if finger.len() != self.ctrl().finger_dof_of(side) as usize {
             error!("Invalid data length of finger");
             return Err("Invalid data length of finger".into());
         }
-
-        let finger_cmd_data = array![
-            finger[0] as f32,
-            finger[1] as f32,
-            finger[2] as f32,
-            finger[3] as f32,
-            finger[4] as f32,
-            finger[5] as f32,
-            0 as f32
-        ];
-
-        match arm_string {
-            "left" => {
-                self.ctrl_mut().set_finger_left(finger_cmd_data);
-            }
-            "right" => {
-                self.ctrl_mut().set_finger_right(finger_cmd_data);
-            }
-            _ => {
-                error!("Invalid arm string");
-                return Err("Invalid arm string".into());
-            }
-        }
-        self.send()?;
-        Ok(())
+        self.ctrl_mut()
+            .set_finger(side, Array1::from_vec(finger.to_vec()));
+        Ok(self)
     }
 }
@@ -0,0 +1,5 @@
+pub mod filter;
+pub mod imu;
+
+pub use filter::{Complementary, LowPass, Madgwick};
+pub use imu::{ImuConfig, ImuEstimator, ImuState, OrientationFilter};
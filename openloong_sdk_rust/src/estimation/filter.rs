@@ -0,0 +1,159 @@
+use std::f64::consts::PI;
+
+use crate::geometry::Quat;
+
+/// First-order low-pass over a 3-vector.
+#[derive(Clone, Debug)]
+pub struct LowPass {
+    cutoff_hz: f64,
+    state: Option<[f64; 3]>,
+}
+
+impl LowPass {
+    /// A non-positive cutoff disables filtering.
+    pub fn new(cutoff_hz: f64) -> Self {
+        Self {
+            cutoff_hz,
+            state: None,
+        }
+    }
+
+    pub fn set_cutoff_hz(&mut self, cutoff_hz: f64) -> &mut Self {
+        self.cutoff_hz = cutoff_hz;
+        self
+    }
+
+    pub fn reset(&mut self) {
+        self.state = None;
+    }
+
+    pub fn value(&self) -> Option<[f64; 3]> {
+        self.state
+    }
+
+    pub fn step(&mut self, x: [f64; 3], dt: f64) -> [f64; 3] {
+        let y = match self.state {
+            Some(prev) if self.cutoff_hz > 0.0 && dt > 0.0 => {
+                let rc = 1.0 / (2.0 * PI * self.cutoff_hz);
+                let alpha = dt / (rc + dt);
+                [
+                    prev[0] + alpha * (x[0] - prev[0]),
+                    prev[1] + alpha * (x[1] - prev[1]),
+                    prev[2] + alpha * (x[2] - prev[2]),
+                ]
+            }
+            _ => x,
+        };
+        self.state = Some(y);
+        y
+    }
+}
+
+/// Roll/pitch from gyro integration corrected by the accelerometer tilt.
+///
+/// `alpha` close to 1 trusts the gyro; yaw is integrated from the gyro only.
+#[derive(Clone, Debug)]
+pub struct Complementary {
+    alpha: f64,
+    rpy: Option<[f64; 3]>,
+}
+
+impl Complementary {
+    pub fn new(alpha: f64) -> Self {
+        Self {
+            alpha: alpha.clamp(0.0, 1.0),
+            rpy: None,
+        }
+    }
+
+    pub fn reset(&mut self, rpy: [f64; 3]) {
+        self.rpy = Some(rpy);
+    }
+
+    pub fn rpy(&self) -> Option<[f64; 3]> {
+        self.rpy
+    }
+
+    pub fn step(&mut self, gyr: [f64; 3], acc: [f64; 3], dt: f64) -> [f64; 3] {
+        let roll_acc = acc[1].atan2(acc[2]);
+        let pitch_acc = (-acc[0]).atan2((acc[1] * acc[1] + acc[2] * acc[2]).sqrt());
+        let rpy = match self.rpy {
+            Some(prev) => [
+                self.alpha * (prev[0] + gyr[0] * dt) + (1.0 - self.alpha) * roll_acc,
+                self.alpha * (prev[1] + gyr[1] * dt) + (1.0 - self.alpha) * pitch_acc,
+                prev[2] + gyr[2] * dt,
+            ],
+            None => [roll_acc, pitch_acc, 0.0],
+        };
+        self.rpy = Some(rpy);
+        rpy
+    }
+}
+
+/// Madgwick gradient-descent orientation filter, IMU variant (gyro + acc).
+///
+/// The quaternion is `world_R_imu`; `beta` weights the accelerometer
+/// correction against gyro integration.
+#[derive(Clone, Debug)]
+pub struct Madgwick {
+    beta: f64,
+    q: Quat,
+}
+
+impl Madgwick {
+    pub fn new(beta: f64) -> Self {
+        Self {
+            beta,
+            q: Quat::IDENTITY,
+        }
+    }
+
+    pub fn reset(&mut self, q: Quat) {
+        self.q = q;
+    }
+
+    pub fn quat(&self) -> Quat {
+        self.q
+    }
+
+    pub fn step(&mut self, gyr: [f64; 3], acc: [f64; 3], dt: f64) -> Quat {
+        let Quat {
+            w: q0,
+            x: q1,
+            y: q2,
+            z: q3,
+        } = self.q;
+        let [gx, gy, gz] = gyr;
+
+        let mut dq0 = 0.5 * (-q1 * gx - q2 * gy - q3 * gz);
+        let mut dq1 = 0.5 * (q0 * gx + q2 * gz - q3 * gy);
+        let mut dq2 = 0.5 * (q0 * gy - q1 * gz + q3 * gx);
+        let mut dq3 = 0.5 * (q0 * gz + q1 * gy - q2 * gx);
+
+        let n = (acc[0] * acc[0] + acc[1] * acc[1] + acc[2] * acc[2]).sqrt();
+        if n > f64::EPSILON {
+            let (ax, ay, az) = (acc[0] / n, acc[1] / n, acc[2] / n);
+            let (q0q0, q1q1, q2q2, q3q3) = (q0 * q0, q1 * q1, q2 * q2, q3 * q3);
+            let s0 = 4.0 * q0 * q2q2 + 2.0 * q2 * ax + 4.0 * q0 * q1q1 - 2.0 * q1 * ay;
+            let s1 = 4.0 * q1 * q3q3 - 2.0 * q3 * ax + 4.0 * q0q0 * q1 - 2.0 * q0 * ay - 4.0 * q1
+                + 8.0 * q1 * q1q1
+                + 8.0 * q1 * q2q2
+                + 4.0 * q1 * az;
+            let s2 = 4.0 * q0q0 * q2 + 2.0 * q0 * ax + 4.0 * q2 * q3q3 - 2.0 * q3 * ay - 4.0 * q2
+                + 8.0 * q2 * q1q1
+                + 8.0 * q2 * q2q2
+                + 4.0 * q2 * az;
+            let s3 = 4.0 * q1q1 * q3 - 2.0 * q1 * ax + 4.0 * q2q2 * q3 - 2.0 * q2 * ay;
+            let sn = (s0 * s0 + s1 * s1 + s2 * s2 + s3 * s3).sqrt();
+            if sn > f64::EPSILON {
+                dq0 -= self.beta * s0 / sn;
+                dq1 -= self.beta * s1 / sn;
+                dq2 -= self.beta * s2 / sn;
+                dq3 -= self.beta * s3 / sn;
+            }
+        }
+
+        self.q = Quat::new(q0 + dq0 * dt, q1 + dq1 * dt, q2 + dq2 * dt, q3 + dq3 * dt);
+        self.q
+    }
+}
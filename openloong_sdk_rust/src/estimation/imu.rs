@@ -0,0 +1,165 @@
+use tracing::warn;
+
+use crate::estimation::filter::{Complementary, LowPass, Madgwick};
+use crate::geometry::Quat;
+use crate::sdk::sens::SensData;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrientationFilter {
+    /// Use the `rpy` reported by the robot as is.
+    Raw,
+    Complementary,
+    Madgwick,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
+#[serde(default)]
+pub struct ImuConfig {
+    pub orientation: OrientationFilter,
+    pub gyr_cutoff_hz: f64,
+    pub acc_cutoff_hz: f64,
+    pub complementary_alpha: f64,
+    pub madgwick_beta: f64,
+    /// Tilt from upright, in radians, above which the robot counts as falling.
+    pub fall_tilt: f64,
+    /// Magnitude of 1 g in the units `acc` is reported in.
+    pub gravity_norm: f64,
+    /// Deviation of `|acc|` from `gravity_norm` flagged as abnormal.
+    pub acc_abnormal: f64,
+    /// Samples further apart than this (seconds), or going back in time,
+    /// restart the filters. A repeated timestamp is skipped.
+    pub max_dt: f64,
+}
+
+impl Default for ImuConfig {
+    fn default() -> Self {
+        Self {
+            orientation: OrientationFilter::Raw,
+            gyr_cutoff_hz: 20.0,
+            acc_cutoff_hz: 5.0,
+            complementary_alpha: 0.98,
+            madgwick_beta: 0.1,
+            fall_tilt: 0.8,
+            gravity_norm: 9.81,
+            acc_abnormal: 6.0,
+            max_dt: 0.5,
+        }
+    }
+}
+
+/// Filtered IMU quantities derived from the latest `SensData`.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct ImuState {
+    pub timestamp: f64,
+    pub rpy: [f64; 3],
+    pub gyr: [f64; 3],
+    pub acc: [f64; 3],
+    /// Unit gravity direction in the IMU frame.
+    pub gravity: [f64; 3],
+    /// Angle between the IMU z axis and the upward vertical, radians.
+    pub tilt: f64,
+    pub falling: bool,
+    pub abnormal_acc: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct ImuEstimator {
+    config: ImuConfig,
+    gyr_lp: LowPass,
+    acc_lp: LowPass,
+    complementary: Complementary,
+    madgwick: Madgwick,
+    state: ImuState,
+    initialized: bool,
+}
+
+impl Default for ImuEstimator {
+    fn default() -> Self {
+        Self::new(ImuConfig::default())
+    }
+}
+
+impl ImuEstimator {
+    pub fn new(config: ImuConfig) -> Self {
+        Self {
+            gyr_lp: LowPass::new(config.gyr_cutoff_hz),
+            acc_lp: LowPass::new(config.acc_cutoff_hz),
+            complementary: Complementary::new(config.complementary_alpha),
+            madgwick: Madgwick::new(config.madgwick_beta),
+            config,
+            state: ImuState::default(),
+            initialized: false,
+        }
+    }
+
+    pub fn config(&self) -> &ImuConfig {
+        &self.config
+    }
+
+    pub fn state(&self) -> &ImuState {
+        &self.state
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new(self.config.clone());
+    }
+
+    /// Replace the config; the filters restart on the next sample.
+    pub fn set_config(&mut self, config: ImuConfig) {
+        *self = Self::new(config);
+    }
+
+    pub fn step(&mut self, sens: &SensData) -> &ImuState {
+        let dt = sens.timestamp - self.state.timestamp;
+        if self.initialized && dt == 0.0 {
+            // same packet again, e.g. a resend
+            return &self.state;
+        }
+        let restart = !self.initialized || dt < 0.0 || dt > self.config.max_dt;
+        let dt = if restart { 0.0 } else { dt };
+
+        let raw_rpy = sens.rpy.map(|v| v as f64);
+        if restart {
+            self.gyr_lp.reset();
+            self.acc_lp.reset();
+            self.complementary.reset(raw_rpy);
+            self.madgwick.reset(Quat::from_rpy(raw_rpy));
+        }
+        let gyr = self.gyr_lp.step(sens.gyr.map(|v| v as f64), dt);
+        let acc = self.acc_lp.step(sens.acc.map(|v| v as f64), dt);
+
+        let rpy = match self.config.orientation {
+            OrientationFilter::Raw => raw_rpy,
+            OrientationFilter::Complementary => self.complementary.step(gyr, acc, dt),
+            OrientationFilter::Madgwick => self.madgwick.step(gyr, acc, dt).to_rpy(),
+        };
+
+        let q = Quat::from_rpy(rpy);
+        let gravity = q.conjugate().rotate([0.0, 0.0, -1.0]);
+        let tilt = (-gravity[2]).clamp(-1.0, 1.0).acos();
+        let acc_norm = (acc[0] * acc[0] + acc[1] * acc[1] + acc[2] * acc[2]).sqrt();
+        let falling = tilt > self.config.fall_tilt;
+        let abnormal_acc = (acc_norm - self.config.gravity_norm).abs() > self.config.acc_abnormal;
+
+        if falling && !self.state.falling {
+            warn!("IMU tilt {:.3} rad exceeds fall threshold", tilt);
+        }
+        if abnormal_acc && !self.state.abnormal_acc {
+            warn!("Abnormal acceleration: |acc| = {:.2} m/s^2", acc_norm);
+        }
+
+        self.state = ImuState {
+            timestamp: sens.timestamp,
+            rpy,
+            gyr,
+            acc,
+            gravity,
+            tilt,
+            falling,
+            abnormal_acc,
+        };
+        self.initialized = true;
+        &self.state
+    }
+}
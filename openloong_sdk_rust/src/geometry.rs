@@ -0,0 +1,17 @@
+//! Rigid-body math for tip poses and frame transforms.
+//!
+//! Roll-pitch-yaw follows the controller convention used by `arm_cmd` and
+//! `*_tip_p_rpy2b`: rotations about the fixed x, y and z axes in that order,
+//! i.e. `R = Rz(yaw) * Ry(pitch) * Rx(roll)`. Angles are in radians.
+
+pub mod frame;
+pub mod gravity;
+pub mod pose;
+pub mod rotation;
+pub mod shape;
+
+pub use frame::{Frame, FrameTree};
+pub use gravity::GravityFrame;
+pub use pose::Pose;
+pub use rotation::{Mat3, Quat};
+pub use shape::Shape;
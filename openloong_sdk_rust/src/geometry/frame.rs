@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+
+use crate::geometry::pose::Pose;
+use crate::sdk::arm::Side;
+use crate::sdk::sens::SensData;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Frame {
+    World,
+    Body,
+    Camera,
+    Tool(Side),
+}
+
+/// Named frames with `World` as the root.
+///
+/// Every other frame stores `parent_T_frame`. A fresh tree places `Body` at
+/// the world origin, and `Camera` and both `Tool` frames on the body origin
+/// until they are set.
+#[derive(Clone, Debug)]
+pub struct FrameTree {
+    links: HashMap<Frame, (Frame, Pose)>,
+}
+
+impl Default for FrameTree {
+    fn default() -> Self {
+        let mut links = HashMap::new();
+        links.insert(Frame::Body, (Frame::World, Pose::IDENTITY));
+        links.insert(Frame::Camera, (Frame::Body, Pose::IDENTITY));
+        links.insert(Frame::Tool(Side::Left), (Frame::Body, Pose::IDENTITY));
+        links.insert(Frame::Tool(Side::Right), (Frame::Body, Pose::IDENTITY));
+        Self { links }
+    }
+}
+
+impl FrameTree {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn parent(&self, frame: Frame) -> Option<Frame> {
+        self.links.get(&frame).map(|(parent, _)| *parent)
+    }
+
+    /// Attach `frame` to `parent` with `parent_T_frame`.
+    pub fn set(
+        &mut self,
+        frame: Frame,
+        parent: Frame,
+        pose: Pose,
+    ) -> Result<&mut Self, Box<dyn std::error::Error>> {
+        if frame == Frame::World {
+            return Err("World is the root frame and has no parent".into());
+        }
+        // walking up from the new parent must not reach the frame itself
+        let mut cur = Some(parent);
+        while let Some(f) = cur {
+            if f == frame {
+                return Err(
+                    format!("Attaching {:?} to {:?} creates a cycle", frame, parent).into(),
+                );
+            }
+            cur = self.parent(f);
+        }
+        self.links.insert(frame, (parent, pose));
+        Ok(self)
+    }
+
+    /// `World_T_frame`
+    pub fn world_pose(&self, frame: Frame) -> Pose {
+        let mut pose = Pose::IDENTITY;
+        let mut cur = frame;
+        while let Some((parent, link)) = self.links.get(&cur) {
+            pose = link.compose(&pose);
+            cur = *parent;
+        }
+        pose
+    }
+
+    /// `to_T_from`, i.e. maps coordinates in `from` into `to`.
+    pub fn transform(&self, from: Frame, to: Frame) -> Pose {
+        self.world_pose(to)
+            .inverse()
+            .compose(&self.world_pose(from))
+    }
+
+    /// Re-express a pose given in `from` in the `to` frame.
+    pub fn express(&self, pose: &Pose, from: Frame, to: Frame) -> Pose {
+        self.transform(from, to).compose(pose)
+    }
+
+    pub fn to_body(&self, pose: &Pose, from: Frame) -> Pose {
+        self.express(pose, from, Frame::Body)
+    }
+
+    /// Move both tool frames to the measured tip poses.
+    pub fn update_tools(&mut self, sens: &SensData) -> &mut Self {
+        for side in Side::BOTH {
+            let tip = Pose::from_p_rpy(&sens.act_tip_p_rpy2b[side.index()]);
+            self.links.insert(Frame::Tool(side), (Frame::Body, tip));
+        }
+        self
+    }
+}
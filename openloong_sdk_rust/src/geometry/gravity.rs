@@ -0,0 +1,108 @@
+use tracing::warn;
+
+use crate::geometry::frame::{Frame, FrameTree};
+use crate::geometry::pose::Pose;
+use crate::geometry::rotation::Quat;
+use crate::sdk::arm::{Side, TipPose};
+use crate::sdk::sens::SensData;
+
+/// Gravity-aligned frame sharing the pelvis heading.
+///
+/// The IMU `rpy` gives the pelvis tilt; dropping its yaw yields a frame whose
+/// z axis points against gravity. When lumbar axes are configured, the torso
+/// rotation from the lumbar joints (the last entries of `act_j`, after arms and
+/// neck) is chained on top so the result describes the body frame the arm
+/// commands are expressed in.
+#[derive(Clone, Debug)]
+pub struct GravityFrame {
+    imu_mount: Quat,
+    lumbar_axes: Vec<[f64; 3]>,
+    level_q_body: Quat,
+}
+
+impl Default for GravityFrame {
+    fn default() -> Self {
+        Self {
+            imu_mount: Quat::IDENTITY,
+            lumbar_axes: Vec::new(),
+            level_q_body: Quat::IDENTITY,
+        }
+    }
+}
+
+impl GravityFrame {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rotation of the IMU relative to the pelvis, `pelvis_R_imu`.
+    pub fn with_imu_mount(mut self, imu_mount: Quat) -> Self {
+        self.imu_mount = imu_mount;
+        self
+    }
+
+    /// Lumbar joint axes from pelvis to torso, in joint order.
+    pub fn with_lumbar_axes(mut self, axes: Vec<[f64; 3]>) -> Self {
+        self.lumbar_axes = axes;
+        self
+    }
+
+    pub fn update(&mut self, sens: &SensData) -> &mut Self {
+        let rpy = sens.rpy.map(|v| v as f64);
+        // world_R_imu without heading, re-expressed for the pelvis
+        let level_q_imu = Quat::from_rpy([rpy[0], rpy[1], 0.0]);
+        let mut q = level_q_imu * self.imu_mount.conjugate();
+
+        let n = self.lumbar_axes.len();
+        if n > 0 {
+            if sens.act_j.len() < n {
+                warn!("act_j too short for {} lumbar joints, ignoring lumbar", n);
+            } else {
+                let lumbar = sens.act_j.slice(ndarray::s![sens.act_j.len() - n..]);
+                for (axis, &angle) in self.lumbar_axes.iter().zip(lumbar.iter()) {
+                    q = q * Quat::from_axis_angle(*axis, angle as f64);
+                }
+            }
+        }
+        self.level_q_body = q.normalize();
+        self
+    }
+
+    /// `level_T_body`; the frames share an origin.
+    pub fn level_from_body(&self) -> Pose {
+        Pose::new([0.0; 3], self.level_q_body)
+    }
+
+    pub fn to_level(&self, body_pose: &Pose) -> Pose {
+        self.level_from_body().compose(body_pose)
+    }
+
+    pub fn to_body(&self, level_pose: &Pose) -> Pose {
+        self.level_from_body().inverse().compose(level_pose)
+    }
+
+    /// Torso tilt away from upright, in radians.
+    pub fn tilt(&self) -> f64 {
+        let z = self.level_q_body.rotate([0.0, 0.0, 1.0]);
+        z[2].clamp(-1.0, 1.0).acos()
+    }
+
+    pub fn act_tip(&self, sens: &SensData, side: Side) -> Pose {
+        self.to_level(&Pose::from_p_rpy(&sens.act_tip_p_rpy2b[side.index()]))
+    }
+
+    pub fn tgt_tip(&self, sens: &SensData, side: Side) -> Pose {
+        self.to_level(&Pose::from_p_rpy(&sens.tgt_tip_p_rpy2b[side.index()]))
+    }
+
+    /// Turn a target given in the level frame into a body-frame arm command.
+    pub fn command(&self, level_target: &Pose, elbow_angle: f32) -> TipPose {
+        self.to_body(level_target).to_tip(elbow_angle)
+    }
+
+    /// Use this frame as `World` and hang `Body` below it.
+    pub fn apply(&self, tree: &mut FrameTree) {
+        // Body only ever hangs off World, so this cannot form a cycle
+        let _ = tree.set(Frame::Body, Frame::World, self.level_from_body());
+    }
+}
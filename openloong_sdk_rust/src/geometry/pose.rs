@@ -0,0 +1,116 @@
+use std::ops::Mul;
+
+use crate::geometry::rotation::{Quat, unwrap_rpy};
+use crate::sdk::arm::TipPose;
+
+/// Rigid transform `a_T_b`: position of frame `b` in `a` plus its orientation.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Pose {
+    pub p: [f64; 3],
+    pub q: Quat,
+}
+
+impl Pose {
+    pub const IDENTITY: Pose = Pose {
+        p: [0.0; 3],
+        q: Quat::IDENTITY,
+    };
+
+    pub fn new(p: [f64; 3], q: Quat) -> Self {
+        Self { p, q }
+    }
+
+    pub fn from_xyz_rpy(xyz: [f64; 3], rpy: [f64; 3]) -> Self {
+        Self {
+            p: xyz,
+            q: Quat::from_rpy(rpy),
+        }
+    }
+
+    pub fn from_p_rpy(p_rpy: &[f32; 6]) -> Self {
+        Self::from_xyz_rpy(
+            [p_rpy[0] as f64, p_rpy[1] as f64, p_rpy[2] as f64],
+            [p_rpy[3] as f64, p_rpy[4] as f64, p_rpy[5] as f64],
+        )
+    }
+
+    pub fn from_tip(tip: &TipPose) -> Self {
+        Self::from_xyz_rpy(tip.xyz.map(|v| v as f64), tip.rpy.map(|v| v as f64))
+    }
+
+    pub fn to_tip(&self, elbow_angle: f32) -> TipPose {
+        TipPose::new(
+            self.p.map(|v| v as f32),
+            self.rpy().map(|v| v as f32),
+            elbow_angle,
+        )
+    }
+
+    /// [`Pose::to_tip`] for a new command following `previous`: `rpy` is
+    /// unwrapped towards the previous angles with [`unwrap_rpy`], and values
+    /// within float noise (1e-6) of `previous` are kept bit for bit, so an
+    /// unchanged pose gives back the same command.
+    pub fn to_tip_near(&self, elbow_angle: f32, previous: &TipPose) -> TipPose {
+        let rpy = unwrap_rpy(self.rpy(), previous.rpy.map(|v| v as f64));
+        let keep = |new: f64, old: f32| {
+            if (new - old as f64).abs() < 1e-6 {
+                old
+            } else {
+                new as f32
+            }
+        };
+        TipPose::new(
+            std::array::from_fn(|i| keep(self.p[i], previous.xyz[i])),
+            std::array::from_fn(|i| keep(rpy[i], previous.rpy[i])),
+            elbow_angle,
+        )
+    }
+
+    pub fn rpy(&self) -> [f64; 3] {
+        self.q.to_rpy()
+    }
+
+    pub fn inverse(&self) -> Self {
+        let q = self.q.conjugate();
+        let p = q.rotate(self.p);
+        Self {
+            p: [-p[0], -p[1], -p[2]],
+            q,
+        }
+    }
+
+    /// `a_T_c = a_T_b * b_T_c`
+    pub fn compose(&self, other: &Pose) -> Self {
+        let r = self.q.rotate(other.p);
+        Self {
+            p: [self.p[0] + r[0], self.p[1] + r[1], self.p[2] + r[2]],
+            q: (self.q * other.q).normalize(),
+        }
+    }
+
+    pub fn transform_point(&self, v: [f64; 3]) -> [f64; 3] {
+        let r = self.q.rotate(v);
+        [self.p[0] + r[0], self.p[1] + r[1], self.p[2] + r[2]]
+    }
+
+    /// Linear interpolation of position and SLERP of orientation.
+    pub fn interpolate(&self, other: &Pose, t: f64) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        Self {
+            p: [
+                self.p[0] + (other.p[0] - self.p[0]) * t,
+                self.p[1] + (other.p[1] - self.p[1]) * t,
+                self.p[2] + (other.p[2] - self.p[2]) * t,
+            ],
+            q: self.q.slerp(&other.q, t),
+        }
+    }
+}
+
+impl Mul for Pose {
+    type Output = Pose;
+
+    fn mul(self, rhs: Pose) -> Pose {
+        self.compose(&rhs)
+    }
+}
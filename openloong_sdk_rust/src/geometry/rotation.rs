@@ -0,0 +1,274 @@
+use std::f64::consts::{PI, TAU};
+use std::ops::Mul;
+
+/// Row-major 3x3 rotation matrix.
+pub type Mat3 = [[f64; 3]; 3];
+
+/// Unit quaternion `w + xi + yj + zk`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Quat {
+    pub w: f64,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Default for Quat {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Quat {
+    pub const IDENTITY: Quat = Quat {
+        w: 1.0,
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+    };
+
+    pub fn new(w: f64, x: f64, y: f64, z: f64) -> Self {
+        Self { w, x, y, z }.normalize()
+    }
+
+    pub fn from_axis_angle(axis: [f64; 3], angle: f64) -> Self {
+        let n = norm(axis);
+        if n < f64::EPSILON {
+            return Self::IDENTITY;
+        }
+        let (s, c) = (angle / 2.0).sin_cos();
+        Self {
+            w: c,
+            x: axis[0] / n * s,
+            y: axis[1] / n * s,
+            z: axis[2] / n * s,
+        }
+    }
+
+    pub fn from_rpy(rpy: [f64; 3]) -> Self {
+        let (sr, cr) = (rpy[0] / 2.0).sin_cos();
+        let (sp, cp) = (rpy[1] / 2.0).sin_cos();
+        let (sy, cy) = (rpy[2] / 2.0).sin_cos();
+        Self {
+            w: cr * cp * cy + sr * sp * sy,
+            x: sr * cp * cy - cr * sp * sy,
+            y: cr * sp * cy + sr * cp * sy,
+            z: cr * cp * sy - sr * sp * cy,
+        }
+    }
+
+    /// Pitch is clamped to `[-pi/2, pi/2]`; at the singularity roll absorbs
+    /// the combined rotation and yaw is reported as zero.
+    pub fn to_rpy(&self) -> [f64; 3] {
+        matrix_to_rpy(&self.to_matrix())
+    }
+
+    pub fn from_matrix(m: &Mat3) -> Self {
+        let trace = m[0][0] + m[1][1] + m[2][2];
+        let q = if trace > 0.0 {
+            let s = (trace + 1.0).sqrt() * 2.0;
+            Self {
+                w: s / 4.0,
+                x: (m[2][1] - m[1][2]) / s,
+                y: (m[0][2] - m[2][0]) / s,
+                z: (m[1][0] - m[0][1]) / s,
+            }
+        } else if m[0][0] > m[1][1] && m[0][0] > m[2][2] {
+            let s = (1.0 + m[0][0] - m[1][1] - m[2][2]).sqrt() * 2.0;
+            Self {
+                w: (m[2][1] - m[1][2]) / s,
+                x: s / 4.0,
+                y: (m[0][1] + m[1][0]) / s,
+                z: (m[0][2] + m[2][0]) / s,
+            }
+        } else if m[1][1] > m[2][2] {
+            let s = (1.0 + m[1][1] - m[0][0] - m[2][2]).sqrt() * 2.0;
+            Self {
+                w: (m[0][2] - m[2][0]) / s,
+                x: (m[0][1] + m[1][0]) / s,
+                y: s / 4.0,
+                z: (m[1][2] + m[2][1]) / s,
+            }
+        } else {
+            let s = (1.0 + m[2][2] - m[0][0] - m[1][1]).sqrt() * 2.0;
+            Self {
+                w: (m[1][0] - m[0][1]) / s,
+                x: (m[0][2] + m[2][0]) / s,
+                y: (m[1][2] + m[2][1]) / s,
+                z: s / 4.0,
+            }
+        };
+        q.normalize()
+    }
+
+    pub fn to_matrix(&self) -> Mat3 {
+        let Quat { w, x, y, z } = *self;
+        [
+            [
+                1.0 - 2.0 * (y * y + z * z),
+                2.0 * (x * y - w * z),
+                2.0 * (x * z + w * y),
+            ],
+            [
+                2.0 * (x * y + w * z),
+                1.0 - 2.0 * (x * x + z * z),
+                2.0 * (y * z - w * x),
+            ],
+            [
+                2.0 * (x * z - w * y),
+                2.0 * (y * z + w * x),
+                1.0 - 2.0 * (x * x + y * y),
+            ],
+        ]
+    }
+
+    pub fn normalize(self) -> Self {
+        let n = (self.w * self.w + self.x * self.x + self.y * self.y + self.z * self.z).sqrt();
+        if n < f64::EPSILON {
+            return Self::IDENTITY;
+        }
+        Self {
+            w: self.w / n,
+            x: self.x / n,
+            y: self.y / n,
+            z: self.z / n,
+        }
+    }
+
+    pub fn conjugate(&self) -> Self {
+        Self {
+            w: self.w,
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+
+    pub fn dot(&self, other: &Quat) -> f64 {
+        self.w * other.w + self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// Angle of the shortest rotation taking `self` to `other`.
+    pub fn angle_to(&self, other: &Quat) -> f64 {
+        2.0 * self.dot(other).abs().min(1.0).acos()
+    }
+
+    /// Axis times angle, the angle in `[0, pi]`.
+    pub fn to_rotation_vector(&self) -> [f64; 3] {
+        let q = if self.w < 0.0 {
+            Quat {
+                w: -self.w,
+                x: -self.x,
+                y: -self.y,
+                z: -self.z,
+            }
+        } else {
+            *self
+        };
+        let s = norm([q.x, q.y, q.z]);
+        if s < 1e-12 {
+            return [2.0 * q.x, 2.0 * q.y, 2.0 * q.z];
+        }
+        let k = 2.0 * s.atan2(q.w) / s;
+        [q.x * k, q.y * k, q.z * k]
+    }
+
+    pub fn rotate(&self, v: [f64; 3]) -> [f64; 3] {
+        mat_vec(&self.to_matrix(), v)
+    }
+
+    /// Spherical linear interpolation along the shortest arc, `t` in `[0, 1]`.
+    pub fn slerp(&self, other: &Quat, t: f64) -> Self {
+        let mut end = *other;
+        let mut d = self.dot(other);
+        if d < 0.0 {
+            end = Quat {
+                w: -end.w,
+                x: -end.x,
+                y: -end.y,
+                z: -end.z,
+            };
+            d = -d;
+        }
+        let (a, b) = if d > 0.9995 {
+            // nearly parallel: fall back to nlerp to avoid dividing by ~0
+            (1.0 - t, t)
+        } else {
+            let theta = d.acos();
+            let s = theta.sin();
+            (((1.0 - t) * theta).sin() / s, (t * theta).sin() / s)
+        };
+        Quat {
+            w: a * self.w + b * end.w,
+            x: a * self.x + b * end.x,
+            y: a * self.y + b * end.y,
+            z: a * self.z + b * end.z,
+        }
+        .normalize()
+    }
+}
+
+impl Mul for Quat {
+    type Output = Quat;
+
+    fn mul(self, r: Quat) -> Quat {
+        Quat {
+            w: self.w * r.w - self.x * r.x - self.y * r.y - self.z * r.z,
+            x: self.w * r.x + self.x * r.w + self.y * r.z - self.z * r.y,
+            y: self.w * r.y - self.x * r.z + self.y * r.w + self.z * r.x,
+            z: self.w * r.z + self.x * r.y - self.y * r.x + self.z * r.w,
+        }
+    }
+}
+
+pub fn rpy_to_matrix(rpy: [f64; 3]) -> Mat3 {
+    Quat::from_rpy(rpy).to_matrix()
+}
+
+pub fn matrix_to_rpy(m: &Mat3) -> [f64; 3] {
+    let sp = (-m[2][0]).clamp(-1.0, 1.0);
+    let pitch = sp.asin();
+    if sp.abs() > 1.0 - 1e-9 {
+        // gimbal lock, only roll +/- yaw is observable
+        return [(-m[1][2]).atan2(m[1][1]), pitch, 0.0];
+    }
+    [m[2][1].atan2(m[2][2]), pitch, m[1][0].atan2(m[0][0])]
+}
+
+/// `angle` shifted by whole turns to lie within half a turn of `near`.
+pub fn wrap_near(angle: f64, near: f64) -> f64 {
+    angle - TAU * ((angle - near) / TAU).round()
+}
+
+/// Roll-pitch-yaw of the same rotation as `rpy`, as close as possible to
+/// `near`, e.g. the previous command. The controller interpolates the angles
+/// themselves, so a roll of pi reported as -pi would spin the wrist a full
+/// turn. Picks between the two solutions `(r, p, y)` and
+/// `(r + pi, pi - p, y + pi)` after shifting each angle by whole turns; at the
+/// pitch singularity yaw is kept at `near` and roll takes the rest.
+pub fn unwrap_rpy(rpy: [f64; 3], near: [f64; 3]) -> [f64; 3] {
+    let [roll, pitch, yaw] = rpy;
+    if pitch.cos().abs() < 1e-6 {
+        // only roll - yaw (pitch +pi/2) or roll + yaw (-pi/2) is observable
+        let roll = roll + pitch.signum() * (near[2] - yaw);
+        return [wrap_near(roll, near[0]), pitch, near[2]];
+    }
+    let distance = |c: &[f64; 3]| (0..3).map(|i| (c[i] - near[i]).abs()).sum::<f64>();
+    [[roll, pitch, yaw], [roll + PI, PI - pitch, yaw + PI]]
+        .map(|c| std::array::from_fn(|i| wrap_near(c[i], near[i])))
+        .into_iter()
+        .min_by(|a, b| distance(a).total_cmp(&distance(b)))
+        .unwrap()
+}
+
+pub fn mat_vec(m: &Mat3, v: [f64; 3]) -> [f64; 3] {
+    [
+        m[0][0] * v[0] + m[0][1] * v[1] + m[0][2] * v[2],
+        m[1][0] * v[0] + m[1][1] * v[1] + m[1][2] * v[2],
+        m[2][0] * v[0] + m[2][1] * v[1] + m[2][2] * v[2],
+    ]
+}
+
+pub fn norm(v: [f64; 3]) -> f64 {
+    (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt()
+}
@@ -0,0 +1,99 @@
+use crate::geometry::pose::Pose;
+use crate::geometry::rotation::norm;
+
+/// Collision primitive. A sphere is treated as a capsule of zero length.
+#[derive(Copy, Clone, Debug, PartialEq, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Shape {
+    Sphere {
+        center: [f64; 3],
+        radius: f64,
+    },
+    /// Segment `a`-`b` swept by a sphere.
+    Capsule {
+        a: [f64; 3],
+        b: [f64; 3],
+        radius: f64,
+    },
+}
+
+impl Shape {
+    pub fn radius(&self) -> f64 {
+        match *self {
+            Shape::Sphere { radius, .. } | Shape::Capsule { radius, .. } => radius,
+        }
+    }
+
+    /// Core segment, both ends equal for a sphere.
+    pub fn segment(&self) -> ([f64; 3], [f64; 3]) {
+        match *self {
+            Shape::Sphere { center, .. } => (center, center),
+            Shape::Capsule { a, b, .. } => (a, b),
+        }
+    }
+
+    /// The same shape given in the frame `pose` is expressed in.
+    pub fn transformed(&self, pose: &Pose) -> Shape {
+        match *self {
+            Shape::Sphere { center, radius } => Shape::Sphere {
+                center: pose.transform_point(center),
+                radius,
+            },
+            Shape::Capsule { a, b, radius } => Shape::Capsule {
+                a: pose.transform_point(a),
+                b: pose.transform_point(b),
+                radius,
+            },
+        }
+    }
+
+    /// Gap between the surfaces, negative when they overlap.
+    pub fn distance(&self, other: &Shape) -> f64 {
+        let (a, b) = self.segment();
+        let (c, d) = other.segment();
+        segment_distance(a, b, c, d) - self.radius() - other.radius()
+    }
+}
+
+/// Shortest distance between segments `p1`-`q1` and `p2`-`q2`.
+pub fn segment_distance(p1: [f64; 3], q1: [f64; 3], p2: [f64; 3], q2: [f64; 3]) -> f64 {
+    let sub = |a: [f64; 3], b: [f64; 3]| [a[0] - b[0], a[1] - b[1], a[2] - b[2]];
+    let dot = |a: [f64; 3], b: [f64; 3]| a[0] * b[0] + a[1] * b[1] + a[2] * b[2];
+    let d1 = sub(q1, p1);
+    let d2 = sub(q2, p2);
+    let r = sub(p1, p2);
+    let (a, e, f) = (dot(d1, d1), dot(d2, d2), dot(d2, r));
+    const EPS: f64 = 1e-12;
+
+    // closest points p1 + s d1 and p2 + t d2, clamped to both segments
+    let (s, t) = if a <= EPS && e <= EPS {
+        (0.0, 0.0)
+    } else if a <= EPS {
+        (0.0, (f / e).clamp(0.0, 1.0))
+    } else {
+        let c = dot(d1, r);
+        if e <= EPS {
+            ((-c / a).clamp(0.0, 1.0), 0.0)
+        } else {
+            let b = dot(d1, d2);
+            let denom = a * e - b * b;
+            let mut s = if denom > EPS {
+                ((b * f - c * e) / denom).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let mut t = (b * s + f) / e;
+            if t < 0.0 {
+                t = 0.0;
+                s = (-c / a).clamp(0.0, 1.0);
+            } else if t > 1.0 {
+                t = 1.0;
+                s = ((b - c) / a).clamp(0.0, 1.0);
+            }
+            (s, t)
+        }
+    };
+    let c1: [f64; 3] = std::array::from_fn(|i| p1[i] + d1[i] * s);
+    let c2: [f64; 3] = std::array::from_fn(|i| p2[i] + d2[i] * t);
+    norm(sub(c1, c2))
+}
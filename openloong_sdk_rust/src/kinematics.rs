@@ -0,0 +1,14 @@
+//! Forward kinematics of the arms, neck and lumbar from joint angles.
+//!
+//! Each limb is a serial [`Chain`] of revolute joints, described either the
+//! URDF way (fixed origin plus rotation axis) or by a standard DH row. The
+//! lumbar chain runs from the body frame to the torso; arms and neck hang off
+//! the torso.
+
+pub mod chain;
+pub mod ik;
+pub mod robot;
+
+pub use chain::{Chain, Dh, Joint};
+pub use ik::{IkConfig, IkSolution};
+pub use robot::{Drift, KinematicsConfig, LinkPoses};
@@ -0,0 +1,200 @@
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use crate::geometry::{Pose, Quat};
+
+/// Standard Denavit-Hartenberg row, `Rz(theta + q) Tz(d) Tx(a) Rx(alpha)`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, serde::Deserialize)]
+#[serde(default)]
+pub struct Dh {
+    pub a: f64,
+    pub alpha: f64,
+    pub d: f64,
+    /// Joint angle offset, radians.
+    pub theta: f64,
+}
+
+/// One revolute joint with its limits, radians.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
+#[serde(default)]
+pub struct Joint {
+    pub name: String,
+    /// Joint frame in the previous one, `[x, y, z, roll, pitch, yaw]`.
+    pub origin: [f64; 6],
+    /// Rotation axis in the joint frame.
+    pub axis: [f64; 3],
+    /// Replaces `origin` and `axis` when set.
+    pub dh: Option<Dh>,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl Default for Joint {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            origin: [0.0; 6],
+            axis: [0.0, 0.0, 1.0],
+            dh: None,
+            min: -PI,
+            max: PI,
+        }
+    }
+}
+
+impl Joint {
+    pub fn new(name: &str, origin: [f64; 6], axis: [f64; 3]) -> Self {
+        Self {
+            name: name.to_string(),
+            origin,
+            axis,
+            ..Default::default()
+        }
+    }
+
+    pub fn from_dh(name: &str, dh: Dh) -> Self {
+        Self {
+            name: name.to_string(),
+            dh: Some(dh),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_limits(mut self, min: f64, max: f64) -> Self {
+        self.min = min;
+        self.max = max;
+        self
+    }
+
+    /// `previous_T_joint` at angle `q`.
+    pub fn transform(&self, q: f64) -> Pose {
+        match &self.dh {
+            Some(dh) => {
+                let rz = Pose::new(
+                    [0.0, 0.0, dh.d],
+                    Quat::from_axis_angle([0.0, 0.0, 1.0], dh.theta + q),
+                );
+                let rx = Pose::new(
+                    [dh.a, 0.0, 0.0],
+                    Quat::from_axis_angle([1.0, 0.0, 0.0], dh.alpha),
+                );
+                rz.compose(&rx)
+            }
+            None => pose6(&self.origin)
+                .compose(&Pose::new([0.0; 3], Quat::from_axis_angle(self.axis, q))),
+        }
+    }
+
+    pub fn clamp(&self, q: f64) -> f64 {
+        q.clamp(self.min, self.max)
+    }
+}
+
+/// Serial chain from a parent frame through its joints to a tool frame.
+#[derive(Clone, Debug, Default, PartialEq, serde::Deserialize)]
+#[serde(default)]
+pub struct Chain {
+    /// First joint's reference frame in the parent, `[x, y, z, roll, pitch, yaw]`.
+    pub base: [f64; 6],
+    pub joints: Vec<Joint>,
+    /// Tool frame in the last joint frame.
+    pub tool: [f64; 6],
+}
+
+impl Chain {
+    pub fn dof(&self) -> usize {
+        self.joints.len()
+    }
+
+    /// Frame of every joint, then the tool, in the frame `parent` is given
+    /// in. Missing angles count as zero, extra ones are ignored.
+    pub fn forward(&self, parent: &Pose, q: &[f64]) -> Vec<Pose> {
+        let mut frame = parent.compose(&pose6(&self.base));
+        let mut frames = Vec::with_capacity(self.dof() + 1);
+        for (i, joint) in self.joints.iter().enumerate() {
+            frame = frame.compose(&joint.transform(q.get(i).copied().unwrap_or(0.0)));
+            frames.push(frame);
+        }
+        frames.push(frame.compose(&pose6(&self.tool)));
+        frames
+    }
+
+    /// Tool frame only.
+    pub fn tip(&self, parent: &Pose, q: &[f64]) -> Pose {
+        // forward always ends with the tool frame
+        *self.forward(parent, q).last().unwrap()
+    }
+
+    /// The same chain reflected across the x-z plane, for the other arm.
+    /// Joint angles keep their meaning: equal angles give mirrored poses.
+    pub fn mirrored(&self) -> Self {
+        Self {
+            base: mirror6(&self.base),
+            joints: self
+                .joints
+                .iter()
+                .map(|j| Joint {
+                    origin: mirror6(&j.origin),
+                    axis: [-j.axis[0], j.axis[1], -j.axis[2]],
+                    ..j.clone()
+                })
+                .collect(),
+            tool: mirror6(&self.tool),
+        }
+    }
+
+    /// Placeholder Loong left arm: shoulder pitch, roll, yaw, elbow, forearm
+    /// yaw, wrist pitch and roll, arm hanging down at zero. The tool x axis
+    /// points away from the forearm.
+    pub fn loong_arm() -> Self {
+        let limb = |name: &str, z: f64, axis: [f64; 3]| {
+            Joint::new(name, [0.0, 0.0, z, 0.0, 0.0, 0.0], axis)
+        };
+        Self {
+            base: [0.0, 0.2, 0.1, 0.0, 0.0, 0.0],
+            joints: vec![
+                limb("shoulder_pitch", 0.0, [0.0, 1.0, 0.0]),
+                limb("shoulder_roll", 0.0, [1.0, 0.0, 0.0]).with_limits(-0.5, PI),
+                limb("shoulder_yaw", 0.0, [0.0, 0.0, 1.0]),
+                limb("elbow", -0.3, [0.0, 1.0, 0.0]).with_limits(-2.6, 0.0),
+                limb("forearm_yaw", 0.0, [0.0, 0.0, 1.0]),
+                limb("wrist_pitch", -0.28, [0.0, 1.0, 0.0]).with_limits(-1.2, 1.2),
+                limb("wrist_roll", 0.0, [1.0, 0.0, 0.0]).with_limits(-1.2, 1.2),
+            ],
+            tool: [0.0, 0.0, -0.1, 0.0, FRAC_PI_2, 0.0],
+        }
+    }
+
+    /// Yaw then pitch about the neck pivot, tool at the eyes.
+    pub fn loong_neck() -> Self {
+        Self {
+            base: [0.0, 0.0, 0.45, 0.0, 0.0, 0.0],
+            joints: vec![
+                Joint::new("neck_yaw", [0.0; 6], [0.0, 0.0, 1.0]).with_limits(-1.2, 1.2),
+                Joint::new("neck_pitch", [0.0; 6], [0.0, 1.0, 0.0]).with_limits(-0.5, 0.9),
+            ],
+            tool: [0.0, 0.0, 0.1, 0.0, 0.0, 0.0],
+        }
+    }
+
+    /// Roll, pitch and yaw about the lumbar pivot, ending in the torso frame,
+    /// which equals the body frame when upright.
+    pub fn loong_lumbar() -> Self {
+        Self {
+            base: [0.0, 0.0, -0.35, 0.0, 0.0, 0.0],
+            joints: vec![
+                Joint::new("lumbar_roll", [0.0; 6], [1.0, 0.0, 0.0]).with_limits(-0.2, 0.2),
+                Joint::new("lumbar_pitch", [0.0; 6], [0.0, 1.0, 0.0]).with_limits(-0.1, 0.5),
+                Joint::new("lumbar_yaw", [0.0; 6], [0.0, 0.0, 1.0]).with_limits(-0.5, 0.5),
+            ],
+            tool: [0.0, 0.0, 0.35, 0.0, 0.0, 0.0],
+        }
+    }
+}
+
+fn pose6(v: &[f64; 6]) -> Pose {
+    Pose::from_xyz_rpy([v[0], v[1], v[2]], [v[3], v[4], v[5]])
+}
+
+fn mirror6(v: &[f64; 6]) -> [f64; 6] {
+    [v[0], -v[1], v[2], -v[3], v[4], -v[5]]
+}
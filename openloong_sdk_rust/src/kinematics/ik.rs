@@ -0,0 +1,314 @@
+use tracing::error;
+
+use crate::geometry::Pose;
+use crate::geometry::rotation::norm;
+use crate::kinematics::chain::Chain;
+use crate::sdk::LoongManiSdk;
+use crate::sdk::arm::{Side, TipPose};
+use crate::sdk::ctrl::ArmMode;
+
+/// `[kinematics.ik]` table: damped least squares on the tip pose, with the
+/// arm angle solved in the null space of the tip task.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
+#[serde(default)]
+pub struct IkConfig {
+    /// Damping of the least-squares steps, larger is steadier near
+    /// singularities and slower to converge.
+    pub damping: f64,
+    pub max_iterations: usize,
+    /// Largest joint change per iteration, radians.
+    pub max_step: f64,
+    /// Convergence thresholds: tip position (m), tip orientation and arm
+    /// angle (rad).
+    pub position_tolerance: f64,
+    pub angle_tolerance: f64,
+    pub arm_angle_tolerance: f64,
+    /// Link frames whose origins are the shoulder, elbow and wrist centers,
+    /// indices into the chain's joint frames.
+    pub shoulder: usize,
+    pub elbow: usize,
+    pub wrist: usize,
+}
+
+impl Default for IkConfig {
+    fn default() -> Self {
+        Self {
+            damping: 0.02,
+            max_iterations: 100,
+            max_step: 0.2,
+            position_tolerance: 1e-4,
+            angle_tolerance: 1e-3,
+            arm_angle_tolerance: 1e-2,
+            shoulder: 0,
+            elbow: 3,
+            wrist: 5,
+        }
+    }
+}
+
+/// Result of [`IkConfig::solve`]; `joints` is the best found even when not
+/// converged.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IkSolution {
+    pub joints: Vec<f64>,
+    pub converged: bool,
+    pub iterations: usize,
+    pub position_error: f64,
+    pub angle_error: f64,
+    pub arm_angle_error: f64,
+}
+
+impl IkConfig {
+    /// Swivel of the elbow about the shoulder-wrist line, zero with the elbow
+    /// straight below it and positive when it moves outwards, away from the
+    /// body, for either arm.
+    pub fn arm_angle(&self, chain: &Chain, parent: &Pose, q: &[f64], side: Side) -> f64 {
+        self.arm_angle_of(&chain.forward(parent, q), parent, side)
+    }
+
+    fn arm_angle_of(&self, frames: &[Pose], parent: &Pose, side: Side) -> f64 {
+        let at = |i: usize| frames.get(i).map(|f| f.p).unwrap_or_default();
+        let (s, e, w) = (at(self.shoulder), at(self.elbow), at(self.wrist));
+        let axis = unit(sub(w, s));
+        // reference: the parent's down direction, off the shoulder-wrist line
+        let down = parent.q.rotate([0.0, 0.0, -1.0]);
+        let reference = unit(reject(down, axis));
+        let elbow = reject(sub(e, s), axis);
+        let angle = dot(cross(reference, elbow), axis).atan2(dot(reference, elbow));
+        // positive about the forward shoulder-wrist line swings the left elbow
+        // outwards, the right one inwards
+        match side {
+            Side::Left => angle,
+            Side::Right => -angle,
+        }
+    }
+
+    /// Joint angles putting the tool of `chain` at `target` with the given
+    /// arm angle, starting from `seed`. Both poses are in the frame `parent`
+    /// is given in; joints stay within the chain's limits.
+    pub fn solve(
+        &self,
+        chain: &Chain,
+        parent: &Pose,
+        target: &Pose,
+        arm_angle: f64,
+        side: Side,
+        seed: &[f64],
+    ) -> IkSolution {
+        let n = chain.dof();
+        let mut q: Vec<f64> = (0..n)
+            .map(|i| chain.joints[i].clamp(seed.get(i).copied().unwrap_or(0.0)))
+            .collect();
+        let lambda2 = self.damping * self.damping;
+        let mut solution = IkSolution {
+            joints: q.clone(),
+            converged: false,
+            iterations: 0,
+            position_error: f64::INFINITY,
+            angle_error: f64::INFINITY,
+            arm_angle_error: f64::INFINITY,
+        };
+
+        for iteration in 0..=self.max_iterations {
+            let frames = chain.forward(parent, &q);
+            let tip = *frames.last().unwrap();
+            let e = pose_error(&tip, target);
+            let psi = self.arm_angle_of(&frames, parent, side);
+            let e_psi = wrap(arm_angle - psi);
+
+            solution = IkSolution {
+                joints: q.clone(),
+                converged: false,
+                iterations: iteration,
+                position_error: norm([e[0], e[1], e[2]]),
+                angle_error: norm([e[3], e[4], e[5]]),
+                arm_angle_error: e_psi.abs(),
+            };
+            solution.converged = solution.position_error < self.position_tolerance
+                && solution.angle_error < self.angle_tolerance
+                && solution.arm_angle_error < self.arm_angle_tolerance;
+            if solution.converged || iteration == self.max_iterations {
+                break;
+            }
+
+            // numeric jacobians of the tip twist and the arm angle
+            const H: f64 = 1e-6;
+            let mut jac = Vec::with_capacity(n);
+            let mut grad = Vec::with_capacity(n);
+            for i in 0..n {
+                let mut qh = q.clone();
+                qh[i] += H;
+                let fh = chain.forward(parent, &qh);
+                let d = pose_error(&tip, fh.last().unwrap());
+                jac.push(d.map(|v| v / H));
+                grad.push(wrap(self.arm_angle_of(&fh, parent, side) - psi) / H);
+            }
+
+            // primary: dq = J^T (J J^T + l^2 I)^-1 e
+            let mut jjt = [[0.0; 6]; 6];
+            for col in &jac {
+                for r in 0..6 {
+                    for c in 0..6 {
+                        jjt[r][c] += col[r] * col[c];
+                    }
+                }
+            }
+            for (i, row) in jjt.iter_mut().enumerate() {
+                row[i] += lambda2;
+            }
+            let Some(y) = solve6(jjt, e) else {
+                break;
+            };
+            let mut dq: Vec<f64> = jac.iter().map(|col| dot6(col, &y)).collect();
+
+            // secondary: arm angle through N = I - J^T (J J^T + l^2 I)^-1 J
+            let x: Vec<[f64; 6]> = jac.iter().filter_map(|col| solve6(jjt, *col)).collect();
+            if x.len() == n {
+                let ng: Vec<f64> = (0..n)
+                    .map(|a| {
+                        (0..n)
+                            .map(|b| {
+                                let delta = if a == b { 1.0 } else { 0.0 };
+                                (delta - dot6(&jac[a], &x[b])) * grad[b]
+                            })
+                            .sum()
+                    })
+                    .collect();
+                let gain: f64 = grad.iter().zip(&ng).map(|(g, v)| g * v).sum::<f64>() + lambda2;
+                // the tip task moves the arm angle too, correct for it
+                let residual = e_psi - grad.iter().zip(&dq).map(|(g, d)| g * d).sum::<f64>();
+                for i in 0..n {
+                    dq[i] += ng[i] * residual / gain;
+                }
+            }
+
+            let largest = dq.iter().fold(0.0_f64, |m, v| m.max(v.abs()));
+            let scale = if largest > self.max_step {
+                self.max_step / largest
+            } else {
+                1.0
+            };
+            for (i, joint) in chain.joints.iter().enumerate() {
+                q[i] = joint.clamp(q[i] + dq[i] * scale);
+            }
+        }
+        solution
+    }
+}
+
+impl LoongManiSdk {
+    /// Solve the arm joints for a tip pose, with `target.elbow_angle` as the
+    /// arm angle, and write them to `arm_cmd`. Needs `ArmMode::JntAxisCtrl`;
+    /// starts from the commanded joints and uses the measured lumbar angles.
+    /// Commands are left alone when the solver does not converge.
+    pub fn set_tip_ik(
+        &mut self,
+        side: Side,
+        target: &TipPose,
+    ) -> Result<IkSolution, Box<dyn std::error::Error>> {
+        if self.ctrl().arm_mode() != ArmMode::JntAxisCtrl {
+            error!("Client-side IK needs ArmMode::JntAxisCtrl");
+            return Err("Client-side IK needs ArmMode::JntAxisCtrl".into());
+        }
+        let kinematics = self.param().kinematics();
+        let torso = self.link_poses()?.torso();
+        let seed = self.ctrl().arm_joints(side).map(|v| v as f64);
+        let solution = kinematics.ik.solve(
+            kinematics.arm(side),
+            &torso,
+            &Pose::from_tip(target),
+            target.elbow_angle as f64,
+            side,
+            &seed,
+        );
+        if !solution.converged {
+            error!(
+                "{} arm IK did not converge: {:.4} m, {:.4} rad, arm angle {:.4} rad off",
+                side, solution.position_error, solution.angle_error, solution.arm_angle_error
+            );
+            return Err(format!("{} arm IK did not converge", side).into());
+        }
+        let joints = std::array::from_fn(|i| solution.joints[i] as f32);
+        self.ctrl_mut().set_arm_joints(side, joints);
+        Ok(solution)
+    }
+}
+
+/// Twist taking `from` to `to`: position difference and rotation vector,
+/// both in the common frame.
+fn pose_error(from: &Pose, to: &Pose) -> [f64; 6] {
+    let w = (to.q * from.q.conjugate()).to_rotation_vector();
+    [
+        to.p[0] - from.p[0],
+        to.p[1] - from.p[1],
+        to.p[2] - from.p[2],
+        w[0],
+        w[1],
+        w[2],
+    ]
+}
+
+/// Gaussian elimination with partial pivoting.
+fn solve6(mut a: [[f64; 6]; 6], mut b: [f64; 6]) -> Option<[f64; 6]> {
+    for c in 0..6 {
+        let p = (c..6).max_by(|&i, &j| a[i][c].abs().total_cmp(&a[j][c].abs()))?;
+        if a[p][c].abs() < 1e-15 {
+            return None;
+        }
+        a.swap(c, p);
+        b.swap(c, p);
+        for r in c + 1..6 {
+            let f = a[r][c] / a[c][c];
+            let pivot = a[c];
+            for (x, p) in a[r][c..].iter_mut().zip(&pivot[c..]) {
+                *x -= f * p;
+            }
+            b[r] -= f * b[c];
+        }
+    }
+    let mut x = [0.0; 6];
+    for r in (0..6).rev() {
+        let s: f64 = (r + 1..6).map(|k| a[r][k] * x[k]).sum();
+        x[r] = (b[r] - s) / a[r][r];
+    }
+    Some(x)
+}
+
+fn wrap(a: f64) -> f64 {
+    (a + std::f64::consts::PI).rem_euclid(std::f64::consts::TAU) - std::f64::consts::PI
+}
+
+fn dot6(a: &[f64; 6], b: &[f64; 6]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn sub(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+/// Part of `v` perpendicular to the unit vector `axis`.
+fn reject(v: [f64; 3], axis: [f64; 3]) -> [f64; 3] {
+    let d = dot(v, axis);
+    [v[0] - d * axis[0], v[1] - d * axis[1], v[2] - d * axis[2]]
+}
+
+fn unit(v: [f64; 3]) -> [f64; 3] {
+    let n = norm(v);
+    if n < 1e-12 {
+        [0.0; 3]
+    } else {
+        v.map(|x| x / n)
+    }
+}
@@ -0,0 +1,180 @@
+use tracing::{error, warn};
+
+use crate::geometry::Pose;
+use crate::kinematics::chain::Chain;
+use crate::kinematics::ik::IkConfig;
+use crate::sdk::LoongManiSdk;
+use crate::sdk::arm::Side;
+use crate::sdk::sens::SensData;
+
+/// `[kinematics]` table of the param file. The shipped chains are rough
+/// placeholders, replace them with the robot's measured values.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
+#[serde(default)]
+pub struct KinematicsConfig {
+    /// Base in the torso frame.
+    pub left_arm: Chain,
+    pub right_arm: Chain,
+    /// Base in the torso frame.
+    pub neck: Chain,
+    /// Base in the body frame, tool is the torso.
+    pub lumbar: Chain,
+    /// Tip position and orientation mismatch against `act_tip_p_rpy2b`
+    /// reported as drift, meters and radians.
+    pub drift_position: f64,
+    pub drift_angle: f64,
+    pub ik: IkConfig,
+}
+
+impl Default for KinematicsConfig {
+    fn default() -> Self {
+        let arm = Chain::loong_arm();
+        Self {
+            right_arm: arm.mirrored(),
+            left_arm: arm,
+            neck: Chain::loong_neck(),
+            lumbar: Chain::loong_lumbar(),
+            drift_position: 0.02,
+            drift_angle: 0.1,
+            ik: IkConfig::default(),
+        }
+    }
+}
+
+/// Every link frame in the body frame. Joint frames come in chain order and
+/// each list ends with the chain's tool frame.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LinkPoses {
+    pub arms: [Vec<Pose>; 2],
+    pub neck: Vec<Pose>,
+    pub lumbar: Vec<Pose>,
+}
+
+impl LinkPoses {
+    pub fn arm(&self, side: Side) -> &[Pose] {
+        &self.arms[side.index()]
+    }
+
+    /// Tool frame of one arm, comparable to `act_tip_p_rpy2b`.
+    pub fn tip(&self, side: Side) -> Pose {
+        *self.arms[side.index()].last().unwrap()
+    }
+
+    pub fn head(&self) -> Pose {
+        *self.neck.last().unwrap()
+    }
+
+    pub fn torso(&self) -> Pose {
+        *self.lumbar.last().unwrap()
+    }
+}
+
+/// Difference between the computed tip and the controller's own estimate.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Drift {
+    /// Meters.
+    pub position: f64,
+    /// Radians.
+    pub angle: f64,
+}
+
+impl Drift {
+    pub fn between(a: &Pose, b: &Pose) -> Self {
+        let d: [f64; 3] = std::array::from_fn(|i| a.p[i] - b.p[i]);
+        Self {
+            position: (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).sqrt(),
+            angle: a.q.angle_to(&b.q),
+        }
+    }
+
+    pub fn exceeds(&self, config: &KinematicsConfig) -> bool {
+        self.position > config.drift_position || self.angle > config.drift_angle
+    }
+}
+
+impl KinematicsConfig {
+    pub fn arm(&self, side: Side) -> &Chain {
+        match side {
+            Side::Left => &self.left_arm,
+            Side::Right => &self.right_arm,
+        }
+    }
+
+    /// Joints covered, in `act_j` order: left arm, right arm, neck, lumbar.
+    pub fn dof(&self) -> usize {
+        self.left_arm.dof() + self.right_arm.dof() + self.neck.dof() + self.lumbar.dof()
+    }
+
+    /// Link frames for joint angles laid out like `act_j`.
+    pub fn forward(&self, q: &[f32]) -> Result<LinkPoses, Box<dyn std::error::Error>> {
+        if q.len() < self.dof() {
+            return Err(format!(
+                "Expected {} joint angles for kinematics, got {}",
+                self.dof(),
+                q.len()
+            )
+            .into());
+        }
+        let mut rest = q;
+        let mut take = |n: usize| {
+            let (head, tail) = rest.split_at(n);
+            rest = tail;
+            head.iter().map(|&v| v as f64).collect::<Vec<_>>()
+        };
+        let q_left = take(self.left_arm.dof());
+        let q_right = take(self.right_arm.dof());
+        let q_neck = take(self.neck.dof());
+        let q_lumbar = take(self.lumbar.dof());
+
+        let lumbar = self.lumbar.forward(&Pose::IDENTITY, &q_lumbar);
+        let torso = *lumbar.last().unwrap();
+        Ok(LinkPoses {
+            arms: [
+                self.left_arm.forward(&torso, &q_left),
+                self.right_arm.forward(&torso, &q_right),
+            ],
+            neck: self.neck.forward(&torso, &q_neck),
+            lumbar,
+        })
+    }
+
+    /// Computed tips against `act_tip_p_rpy2b`, `[left, right]`.
+    pub fn drift(&self, sens: &SensData) -> Result<[Drift; 2], Box<dyn std::error::Error>> {
+        let links = self.forward(&sens.act_j.to_vec())?;
+        Ok(Side::BOTH.map(|side| {
+            Drift::between(
+                &links.tip(side),
+                &Pose::from_p_rpy(&sens.act_tip_p_rpy2b[side.index()]),
+            )
+        }))
+    }
+}
+
+impl LoongManiSdk {
+    /// Link frames for the measured `act_j`, using `[kinematics]`.
+    pub fn link_poses(&self) -> Result<LinkPoses, Box<dyn std::error::Error>> {
+        let act_j = self.sens().act_j.to_vec();
+        self.param().kinematics().forward(&act_j).inspect_err(|e| {
+            error!("Forward kinematics failed: {}", e);
+        })
+    }
+
+    /// Compare the computed tips with the controller's, warning when they
+    /// differ by more than the `[kinematics]` drift limits.
+    pub fn check_calibration(&self) -> Result<[Drift; 2], Box<dyn std::error::Error>> {
+        let config = self.param().kinematics();
+        let drift = config.drift(self.sens()).inspect_err(|e| {
+            error!("Forward kinematics failed: {}", e);
+        })?;
+        for side in Side::BOTH {
+            let d = drift[side.index()];
+            if d.exceeds(config) {
+                warn!(
+                    "{} arm calibration drift: {:.3} m, {:.3} rad",
+                    side, d.position, d.angle
+                );
+            }
+        }
+        Ok(drift)
+    }
+}
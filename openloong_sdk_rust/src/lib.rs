@@ -1,3 +1,7 @@
 pub mod app;
+pub mod estimation;
+pub mod geometry;
+pub mod kinematics;
+pub mod model;
 pub mod param;
 pub mod sdk;
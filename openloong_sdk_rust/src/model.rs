@@ -0,0 +1,183 @@
+//! Robot description loaded from a URDF: kinematic chains, joint names and
+//! limits in `act_j` order, finger joints and collision geometry.
+
+pub mod urdf;
+
+use std::path::Path;
+
+use crate::geometry::Shape;
+use crate::kinematics::Chain;
+use crate::sdk::arm::Side;
+
+pub use urdf::Urdf;
+
+/// `[urdf]` table of the param file, naming the links the SDK cares about.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
+#[serde(default)]
+pub struct UrdfConfig {
+    /// URDF file, relative paths start at the param file's directory.
+    pub path: String,
+    /// Link the body frame is attached to, where the lumbar chain starts.
+    pub base: String,
+    /// Link carrying the arms and the neck, where the lumbar chain ends.
+    pub torso: String,
+    /// Tool links of the arms, their frames match `*_tip_p_rpy2b`.
+    pub left_hand: String,
+    pub right_hand: String,
+    pub head: String,
+    /// Finger joints in `finger_*` array order.
+    pub left_fingers: Vec<String>,
+    pub right_fingers: Vec<String>,
+}
+
+impl Default for UrdfConfig {
+    fn default() -> Self {
+        Self {
+            path: String::new(),
+            base: "base_link".to_string(),
+            torso: "torso_link".to_string(),
+            left_hand: "left_hand_link".to_string(),
+            right_hand: "right_hand_link".to_string(),
+            head: "head_link".to_string(),
+            left_fingers: Vec::new(),
+            right_fingers: Vec::new(),
+        }
+    }
+}
+
+/// The robot as the URDF describes it, mapped onto the SDK's arrays.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RobotModel {
+    urdf: Urdf,
+    arms: [Chain; 2],
+    neck: Chain,
+    lumbar: Chain,
+    fingers: [Vec<String>; 2],
+}
+
+impl RobotModel {
+    /// Load `config.path`, resolved against `dir` when relative.
+    pub fn load(
+        config: &UrdfConfig,
+        dir: Option<&Path>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let path = Path::new(&config.path);
+        let path = match dir {
+            Some(dir) if path.is_relative() => dir.join(path),
+            _ => path.to_path_buf(),
+        };
+        Self::from_urdf(Urdf::load(path)?, config)
+    }
+
+    pub fn from_urdf(urdf: Urdf, config: &UrdfConfig) -> Result<Self, Box<dyn std::error::Error>> {
+        let arms = [
+            urdf.chain(&config.torso, &config.left_hand)?,
+            urdf.chain(&config.torso, &config.right_hand)?,
+        ];
+        let neck = urdf.chain(&config.torso, &config.head)?;
+        let lumbar = urdf.chain(&config.base, &config.torso)?;
+        for name in config.left_fingers.iter().chain(&config.right_fingers) {
+            match urdf.joint(name) {
+                Some(joint) if joint.is_movable() => {}
+                Some(_) => return Err(format!("Finger joint `{}` is fixed", name).into()),
+                None => return Err(format!("Finger joint `{}` is not in the URDF", name).into()),
+            }
+        }
+        Ok(Self {
+            arms,
+            neck,
+            lumbar,
+            fingers: [config.left_fingers.clone(), config.right_fingers.clone()],
+            urdf,
+        })
+    }
+
+    pub fn urdf(&self) -> &Urdf {
+        &self.urdf
+    }
+
+    pub fn arm(&self, side: Side) -> &Chain {
+        &self.arms[side.index()]
+    }
+
+    pub fn neck(&self) -> &Chain {
+        &self.neck
+    }
+
+    pub fn lumbar(&self) -> &Chain {
+        &self.lumbar
+    }
+
+    /// Arm DOF, when both arms agree.
+    pub fn arm_dof(&self) -> Option<usize> {
+        let [l, r] = &self.arms;
+        (l.dof() == r.dof()).then_some(l.dof())
+    }
+
+    pub fn neck_dof(&self) -> usize {
+        self.neck.dof()
+    }
+
+    pub fn lumbar_dof(&self) -> usize {
+        self.lumbar.dof()
+    }
+
+    pub fn finger_dof(&self, side: Side) -> usize {
+        self.fingers[side.index()].len()
+    }
+
+    /// Length of `act_j`: both arms, neck and lumbar.
+    pub fn jnt_num(&self) -> usize {
+        self.joints().count()
+    }
+
+    fn joints(&self) -> impl Iterator<Item = &crate::kinematics::Joint> {
+        let [l, r] = &self.arms;
+        l.joints
+            .iter()
+            .chain(&r.joints)
+            .chain(&self.neck.joints)
+            .chain(&self.lumbar.joints)
+    }
+
+    /// Joint names in `act_j` / `tgt_j` order.
+    pub fn joint_names(&self) -> Vec<&str> {
+        self.joints().map(|j| j.name.as_str()).collect()
+    }
+
+    /// `[min, max]` per joint in `act_j` order, radians.
+    pub fn joint_limits(&self) -> Vec<[f64; 2]> {
+        self.joints().map(|j| [j.min, j.max]).collect()
+    }
+
+    /// Position of a joint in `act_j`.
+    pub fn joint_index(&self, name: &str) -> Option<usize> {
+        self.joints().position(|j| j.name == name)
+    }
+
+    pub fn finger_names(&self, side: Side) -> &[String] {
+        &self.fingers[side.index()]
+    }
+
+    /// `[min, max]` per finger joint in `finger_*` order.
+    pub fn finger_limits(&self, side: Side) -> Vec<[f64; 2]> {
+        self.fingers[side.index()]
+            .iter()
+            .filter_map(|name| self.urdf.joint(name))
+            .map(|j| j.range())
+            .collect()
+    }
+
+    /// Collision shapes of a link in its own frame; meshes are left out.
+    pub fn collision_shapes(&self, link: &str) -> Vec<Shape> {
+        self.urdf
+            .link(link)
+            .map(|l| {
+                l.collisions
+                    .iter()
+                    .filter_map(|c| c.geometry.to_shape(&c.origin))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,418 @@
+use std::f64::consts::PI;
+use std::path::Path;
+
+use roxmltree::Node;
+
+use crate::geometry::{Pose, Shape};
+use crate::kinematics::{Chain, Joint};
+
+/// Parsed URDF: links with their inertia and collision geometry, and the
+/// joints connecting them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Urdf {
+    pub name: String,
+    pub links: Vec<Link>,
+    pub joints: Vec<UrdfJoint>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Link {
+    pub name: String,
+    pub inertial: Option<Inertial>,
+    pub collisions: Vec<Collision>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Inertial {
+    /// Center of mass frame in the link frame.
+    pub origin: Pose,
+    /// kg
+    pub mass: f64,
+    /// `[ixx, ixy, ixz, iyy, iyz, izz]` about the center of mass, kg m^2.
+    pub inertia: [f64; 6],
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Collision {
+    pub origin: Pose,
+    pub geometry: Geometry,
+}
+
+/// Cylinders and capsules run along the z axis of their origin.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Geometry {
+    Box { size: [f64; 3] },
+    Cylinder { radius: f64, length: f64 },
+    Capsule { radius: f64, length: f64 },
+    Sphere { radius: f64 },
+    Mesh { filename: String, scale: [f64; 3] },
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum JointKind {
+    Revolute,
+    Continuous,
+    Prismatic,
+    Fixed,
+    Floating,
+    Planar,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Limit {
+    pub lower: f64,
+    pub upper: f64,
+    pub effort: f64,
+    pub velocity: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct UrdfJoint {
+    pub name: String,
+    pub kind: JointKind,
+    pub parent: String,
+    pub child: String,
+    /// Child frame in the parent link at zero, `[x, y, z, roll, pitch, yaw]`.
+    pub origin: [f64; 6],
+    pub axis: [f64; 3],
+    pub limit: Option<Limit>,
+}
+
+impl UrdfJoint {
+    pub fn is_movable(&self) -> bool {
+        !matches!(self.kind, JointKind::Fixed)
+    }
+
+    /// `[lower, upper]`, unbounded for continuous joints.
+    pub fn range(&self) -> [f64; 2] {
+        match (self.kind, self.limit) {
+            (JointKind::Continuous, _) => [f64::NEG_INFINITY, f64::INFINITY],
+            (_, Some(limit)) => [limit.lower, limit.upper],
+            (_, None) => [-PI, PI],
+        }
+    }
+}
+
+impl Geometry {
+    /// Collision primitive in the link frame. Cylinders become capsules and
+    /// boxes their bounding spheres; meshes have none.
+    pub fn to_shape(&self, origin: &Pose) -> Option<Shape> {
+        let capsule = |radius: f64, length: f64| Shape::Capsule {
+            a: origin.transform_point([0.0, 0.0, -length / 2.0]),
+            b: origin.transform_point([0.0, 0.0, length / 2.0]),
+            radius,
+        };
+        match *self {
+            Geometry::Sphere { radius } => Some(Shape::Sphere {
+                center: origin.p,
+                radius,
+            }),
+            Geometry::Cylinder { radius, length } | Geometry::Capsule { radius, length } => {
+                Some(capsule(radius, length))
+            }
+            Geometry::Box { size } => Some(Shape::Sphere {
+                center: origin.p,
+                radius: size.iter().map(|s| s * s / 4.0).sum::<f64>().sqrt(),
+            }),
+            Geometry::Mesh { .. } => None,
+        }
+    }
+}
+
+type Error = Box<dyn std::error::Error>;
+
+impl Urdf {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Self::parse(&text).map_err(|e| format!("{}: {}", path.display(), e).into())
+    }
+
+    pub fn parse(text: &str) -> Result<Self, Error> {
+        let doc = roxmltree::Document::parse(text)?;
+        let robot = doc.root_element();
+        if robot.tag_name().name() != "robot" {
+            return Err(format!("Expected <robot>, found <{}>", robot.tag_name().name()).into());
+        }
+        let mut urdf = Urdf {
+            name: robot.attribute("name").unwrap_or_default().to_string(),
+            links: Vec::new(),
+            joints: Vec::new(),
+        };
+        for node in robot.children().filter(Node::is_element) {
+            match node.tag_name().name() {
+                "link" => urdf.links.push(parse_link(node)?),
+                "joint" => urdf.joints.push(parse_joint(node)?),
+                _ => {}
+            }
+        }
+        for joint in &urdf.joints {
+            for link in [&joint.parent, &joint.child] {
+                if urdf.link(link).is_none() {
+                    return Err(format!(
+                        "Joint `{}` refers to unknown link `{}`",
+                        joint.name, link
+                    )
+                    .into());
+                }
+            }
+        }
+        Ok(urdf)
+    }
+
+    pub fn link(&self, name: &str) -> Option<&Link> {
+        self.links.iter().find(|l| l.name == name)
+    }
+
+    pub fn joint(&self, name: &str) -> Option<&UrdfJoint> {
+        self.joints.iter().find(|j| j.name == name)
+    }
+
+    /// Joint whose child is `link`.
+    pub fn parent_joint(&self, link: &str) -> Option<&UrdfJoint> {
+        self.joints.iter().find(|j| j.child == link)
+    }
+
+    /// Joints from `root` down to `tip`, root first.
+    pub fn path(&self, root: &str, tip: &str) -> Result<Vec<&UrdfJoint>, Error> {
+        let mut joints = Vec::new();
+        let mut link = tip;
+        while link != root {
+            let joint = self
+                .parent_joint(link)
+                .ok_or_else(|| format!("Link `{}` is not below `{}`", tip, root))?;
+            joints.push(joint);
+            link = &joint.parent;
+        }
+        joints.reverse();
+        Ok(joints)
+    }
+
+    /// Serial chain from link `root` to link `tip`. Fixed joints are folded
+    /// into their neighbors; only revolute and continuous joints may move.
+    pub fn chain(&self, root: &str, tip: &str) -> Result<Chain, Error> {
+        let mut chain = Chain::default();
+        let mut fixed = Pose::IDENTITY;
+        for joint in self.path(root, tip)? {
+            let origin = fixed.compose(&pose6(&joint.origin));
+            match joint.kind {
+                JointKind::Fixed => fixed = origin,
+                JointKind::Revolute | JointKind::Continuous => {
+                    let [min, max] = joint.range();
+                    chain.joints.push(Joint {
+                        name: joint.name.clone(),
+                        origin: to6(&origin),
+                        axis: joint.axis,
+                        dh: None,
+                        min,
+                        max,
+                    });
+                    fixed = Pose::IDENTITY;
+                }
+                kind => {
+                    return Err(format!(
+                        "Joint `{}` is {:?}, chains only take revolute joints",
+                        joint.name, kind
+                    )
+                    .into());
+                }
+            }
+        }
+        // origins are relative to the previous joint, so the base stays zero
+        chain.tool = to6(&fixed);
+        Ok(chain)
+    }
+}
+
+fn parse_link(node: Node) -> Result<Link, Error> {
+    let name = required(node, "name")?.to_string();
+    let inertial = match child(node, "inertial") {
+        Some(inertial) => {
+            let mass = child(inertial, "mass")
+                .map(|m| number(m, "value"))
+                .transpose()?
+                .unwrap_or(0.0);
+            let inertia = match child(inertial, "inertia") {
+                Some(i) => {
+                    let mut v = [0.0; 6];
+                    for (slot, key) in v.iter_mut().zip(["ixx", "ixy", "ixz", "iyy", "iyz", "izz"])
+                    {
+                        *slot = optional_number(i, key)?.unwrap_or(0.0);
+                    }
+                    v
+                }
+                None => [0.0; 6],
+            };
+            Some(Inertial {
+                origin: pose6(&origin(inertial)?),
+                mass,
+                inertia,
+            })
+        }
+        None => None,
+    };
+    let mut collisions = Vec::new();
+    for collision in node.children().filter(|n| n.has_tag_name("collision")) {
+        let Some(geometry) =
+            child(collision, "geometry").and_then(|g| g.children().find(Node::is_element))
+        else {
+            return Err(format!("Collision of link `{}` has no geometry", name).into());
+        };
+        collisions.push(Collision {
+            origin: pose6(&origin(collision)?),
+            geometry: parse_geometry(geometry)?,
+        });
+    }
+    Ok(Link {
+        name,
+        inertial,
+        collisions,
+    })
+}
+
+fn parse_geometry(node: Node) -> Result<Geometry, Error> {
+    Ok(match node.tag_name().name() {
+        "box" => Geometry::Box {
+            size: vector(node, "size")?.ok_or("<box> needs a size")?,
+        },
+        "cylinder" => Geometry::Cylinder {
+            radius: number(node, "radius")?,
+            length: number(node, "length")?,
+        },
+        "capsule" => Geometry::Capsule {
+            radius: number(node, "radius")?,
+            length: number(node, "length")?,
+        },
+        "sphere" => Geometry::Sphere {
+            radius: number(node, "radius")?,
+        },
+        "mesh" => Geometry::Mesh {
+            filename: required(node, "filename")?.to_string(),
+            scale: vector(node, "scale")?.unwrap_or([1.0; 3]),
+        },
+        other => return Err(format!("Unknown geometry <{}>", other).into()),
+    })
+}
+
+fn parse_joint(node: Node) -> Result<UrdfJoint, Error> {
+    let name = required(node, "name")?.to_string();
+    let kind = match required(node, "type")? {
+        "revolute" => JointKind::Revolute,
+        "continuous" => JointKind::Continuous,
+        "prismatic" => JointKind::Prismatic,
+        "fixed" => JointKind::Fixed,
+        "floating" => JointKind::Floating,
+        "planar" => JointKind::Planar,
+        other => return Err(format!("Joint `{}` has unknown type `{}`", name, other).into()),
+    };
+    let link = |tag: &str| -> Result<String, Error> {
+        child(node, tag)
+            .map(|n| required(n, "link").map(str::to_string))
+            .transpose()?
+            .ok_or_else(|| format!("Joint `{}` has no <{}>", name, tag).into())
+    };
+    let limit = match child(node, "limit") {
+        Some(l) => Some(Limit {
+            lower: optional_number(l, "lower")?.unwrap_or(0.0),
+            upper: optional_number(l, "upper")?.unwrap_or(0.0),
+            effort: optional_number(l, "effort")?.unwrap_or(0.0),
+            velocity: optional_number(l, "velocity")?.unwrap_or(0.0),
+        }),
+        None => None,
+    };
+    if matches!(kind, JointKind::Revolute | JointKind::Prismatic) {
+        let Some(limit) = &limit else {
+            return Err(format!("Joint `{}` needs a <limit>", name).into());
+        };
+        if !limit.lower.is_finite() || !limit.upper.is_finite() {
+            return Err(format!(
+                "Joint `{}` has limit [{}, {}], must be finite",
+                name, limit.lower, limit.upper
+            )
+            .into());
+        }
+    }
+    Ok(UrdfJoint {
+        parent: link("parent")?,
+        child: link("child")?,
+        origin: origin(node)?,
+        axis: match child(node, "axis") {
+            Some(axis) => vector(axis, "xyz")?.unwrap_or([1.0, 0.0, 0.0]),
+            None => [1.0, 0.0, 0.0],
+        },
+        limit,
+        kind,
+        name,
+    })
+}
+
+fn child<'a, 'i>(node: Node<'a, 'i>, tag: &str) -> Option<Node<'a, 'i>> {
+    node.children().find(|n| n.has_tag_name(tag))
+}
+
+fn required<'a>(node: Node<'a, '_>, key: &str) -> Result<&'a str, Error> {
+    node.attribute(key)
+        .ok_or_else(|| format!("<{}> is missing `{}`", node.tag_name().name(), key).into())
+}
+
+fn number(node: Node, key: &str) -> Result<f64, Error> {
+    optional_number(node, key)?
+        .ok_or_else(|| format!("<{}> is missing `{}`", node.tag_name().name(), key).into())
+}
+
+fn optional_number(node: Node, key: &str) -> Result<Option<f64>, Error> {
+    node.attribute(key)
+        .map(|v| {
+            v.trim().parse().map_err(|_| {
+                format!(
+                    "<{}> {}=\"{}\" is not a number",
+                    node.tag_name().name(),
+                    key,
+                    v
+                )
+                .into()
+            })
+        })
+        .transpose()
+}
+
+fn vector(node: Node, key: &str) -> Result<Option<[f64; 3]>, Error> {
+    let Some(text) = node.attribute(key) else {
+        return Ok(None);
+    };
+    let values = text
+        .split_whitespace()
+        .map(str::parse)
+        .collect::<Result<Vec<f64>, _>>()
+        .ok()
+        .filter(|v| v.len() == 3)
+        .ok_or_else(|| {
+            format!(
+                "<{}> {}=\"{}\" needs three numbers",
+                node.tag_name().name(),
+                key,
+                text
+            )
+        })?;
+    Ok(Some([values[0], values[1], values[2]]))
+}
+
+/// `<origin>` child as `[x, y, z, roll, pitch, yaw]`, zero when absent.
+fn origin(node: Node) -> Result<[f64; 6], Error> {
+    let Some(origin) = child(node, "origin") else {
+        return Ok([0.0; 6]);
+    };
+    let xyz = vector(origin, "xyz")?.unwrap_or_default();
+    let rpy = vector(origin, "rpy")?.unwrap_or_default();
+    Ok([xyz[0], xyz[1], xyz[2], rpy[0], rpy[1], rpy[2]])
+}
+
+fn pose6(v: &[f64; 6]) -> Pose {
+    Pose::from_xyz_rpy([v[0], v[1], v[2]], [v[3], v[4], v[5]])
+}
+
+fn to6(pose: &Pose) -> [f64; 6] {
+    let rpy = pose.rpy();
+    [pose.p[0], pose.p[1], pose.p[2], rpy[0], rpy[1], rpy[2]]
+}
@@ -1,4 +1,30 @@
-#[derive(serde::Deserialize)]
+use std::fmt;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+
+use tracing::info;
+
+use crate::app::joystick::JoystickConfig;
+use crate::app::look_at::NeckKinematics;
+use crate::app::posture::LumbarConfig;
+use crate::app::preset_movement::PresetConfig;
+use crate::estimation::ImuConfig;
+use crate::kinematics::KinematicsConfig;
+use crate::model::{RobotModel, UrdfConfig};
+use crate::sdk::arm::{Axis, Side};
+use crate::sdk::collision::CollisionConfig;
+use crate::sdk::ctrl::CtrlInit;
+use crate::sdk::engage::EngageConfig;
+use crate::sdk::hand::HandType;
+use crate::sdk::safety::WorkspaceLimiter;
+use crate::sdk::socket::SocketConfig;
+use crate::sdk::watchdog::WatchdogConfig;
+
+pub mod watch;
+
+pub use watch::ParamWatcher;
+
+#[derive(Clone, Debug, serde::Deserialize)]
 pub struct LoongManiParam {
     jnt_num: i16,
     arm_dof: i16,
@@ -7,6 +33,32 @@ pub struct LoongManiParam {
     neck_dof: i16,
     lumbar_dof: i16,
     target_addr: String,
+    #[serde(default)]
+    init: CtrlInit,
+    #[serde(default)]
+    imu: ImuConfig,
+    #[serde(default)]
+    neck: NeckKinematics,
+    #[serde(default)]
+    lumbar: LumbarConfig,
+    #[serde(default)]
+    engage: EngageConfig,
+    #[serde(default)]
+    preset: PresetConfig,
+    #[serde(default)]
+    joystick: JoystickConfig,
+    #[serde(default)]
+    kinematics: KinematicsConfig,
+    #[serde(default)]
+    socket: SocketConfig,
+    watchdog: Option<WatchdogConfig>,
+    collision: Option<CollisionConfig>,
+    workspace: Option<WorkspaceLimiter>,
+    urdf: Option<UrdfConfig>,
+    #[serde(skip)]
+    model: Option<RobotModel>,
+    #[serde(skip)]
+    profile: Option<String>,
 }
 
 impl LoongManiParam {
@@ -33,10 +85,561 @@ impl LoongManiParam {
         &self.target_addr
     }
 
+    pub fn init(&self) -> &CtrlInit {
+        &self.init
+    }
+
+    pub fn imu(&self) -> &ImuConfig {
+        &self.imu
+    }
+
+    pub fn neck(&self) -> &NeckKinematics {
+        &self.neck
+    }
+
+    pub fn lumbar(&self) -> &LumbarConfig {
+        &self.lumbar
+    }
+
+    pub fn engage(&self) -> &EngageConfig {
+        &self.engage
+    }
+
+    pub fn preset(&self) -> &PresetConfig {
+        &self.preset
+    }
+
+    pub fn joystick(&self) -> &JoystickConfig {
+        &self.joystick
+    }
+
+    pub fn kinematics(&self) -> &KinematicsConfig {
+        &self.kinematics
+    }
+
+    pub fn socket(&self) -> &SocketConfig {
+        &self.socket
+    }
+
+    /// Present when the file has a `[watchdog]` table.
+    pub fn watchdog(&self) -> Option<&WatchdogConfig> {
+        self.watchdog.as_ref()
+    }
+
+    /// Present when the file has a `[collision]` table.
+    pub fn collision(&self) -> Option<&CollisionConfig> {
+        self.collision.as_ref()
+    }
+
+    /// Present when the file has a `[workspace]` table.
+    pub fn workspace(&self) -> Option<&WorkspaceLimiter> {
+        self.workspace.as_ref()
+    }
+
+    /// Present when the file has a `[urdf]` table.
+    pub fn urdf(&self) -> Option<&UrdfConfig> {
+        self.urdf.as_ref()
+    }
+
+    /// Robot loaded from `[urdf]`; its chains replace the `[kinematics]` ones.
+    pub fn model(&self) -> Option<&RobotModel> {
+        self.model.as_ref()
+    }
+
+    /// Name of the `[profile.*]` table applied on load, if any.
+    pub fn profile(&self) -> Option<&str> {
+        self.profile.as_deref()
+    }
+
+    /// Load from `$LOONG_PARAM`, falling back to `./param/param.toml` and then
+    /// to the file shipped with the crate. `$LOONG_PROFILE` selects a profile.
     pub fn read_from_toml() -> Result<Self, Box<dyn std::error::Error>> {
-        const PARAM_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/param/param.toml");
-        let param = std::fs::read_to_string(PARAM_PATH)?;
-        let param: Self = toml::from_str(&param)?;
+        let path = match std::env::var_os(PARAM_ENV) {
+            Some(path) => PathBuf::from(path),
+            None => [PathBuf::from(LOCAL_PARAM_PATH), PathBuf::from(PARAM_PATH)]
+                .into_iter()
+                .find(|p| p.is_file())
+                .ok_or("No param file found, set LOONG_PARAM")?,
+        };
+        let profile = std::env::var(PROFILE_ENV).ok();
+        Self::load_profile(path, profile.as_deref())
+    }
+
+    /// Load a param file without any profile applied.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::load_profile(path, None)
+    }
+
+    /// Load a param file and lay `[profile.<name>]` over its top-level keys.
+    pub fn load_profile(
+        path: impl AsRef<Path>,
+        profile: Option<&str>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let param = Self::parse(&text, profile, path.parent())
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+        info!(
+            "Loaded param from {} (profile: {})",
+            path.display(),
+            profile.unwrap_or("none")
+        );
         Ok(param)
     }
+
+    /// Parse param text; a relative `urdf.path` starts at the current directory.
+    pub fn from_toml_str(
+        text: &str,
+        profile: Option<&str>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::parse(text, profile, None)
+    }
+
+    fn parse(
+        text: &str,
+        profile: Option<&str>,
+        dir: Option<&Path>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut table: toml::Table = toml::from_str(text)?;
+        let mut profiles = match table.remove("profile") {
+            Some(toml::Value::Table(profiles)) => profiles,
+            Some(_) => return Err("`profile` must be a table of profiles".into()),
+            None => toml::Table::new(),
+        };
+        if let Some(name) = profile {
+            match profiles.remove(name) {
+                Some(toml::Value::Table(overlay)) => merge(&mut table, overlay),
+                Some(_) => return Err(format!("`profile.{}` must be a table", name).into()),
+                None => return Err(format!("Unknown profile `{}`", name).into()),
+            }
+        }
+        // DOFs left out of the file come from the URDF
+        let model = match table.get("urdf") {
+            Some(urdf) => {
+                let config: UrdfConfig = urdf.clone().try_into()?;
+                let model = RobotModel::load(&config, dir)?;
+                let mut dofs = vec![
+                    ("jnt_num", model.jnt_num()),
+                    ("neck_dof", model.neck_dof()),
+                    ("lumbar_dof", model.lumbar_dof()),
+                ];
+                dofs.extend(model.arm_dof().map(|dof| ("arm_dof", dof)));
+                for (key, side) in [
+                    ("finger_dof_left", Side::Left),
+                    ("finger_dof_right", Side::Right),
+                ] {
+                    if model.finger_dof(side) > 0 {
+                        dofs.push((key, model.finger_dof(side)));
+                    }
+                }
+                for (key, dof) in dofs {
+                    table.entry(key).or_insert(toml::Value::Integer(dof as i64));
+                }
+                Some(model)
+            }
+            None => None,
+        };
+        let mut param: Self = toml::Value::Table(table).try_into()?;
+        if let Some(model) = model {
+            param.kinematics.left_arm = model.arm(Side::Left).clone();
+            param.kinematics.right_arm = model.arm(Side::Right).clone();
+            param.kinematics.neck = model.neck().clone();
+            param.kinematics.lumbar = model.lumbar().clone();
+            param.model = Some(model);
+        }
+        param.profile = profile.map(str::to_string);
+        param.validate()?;
+        Ok(param)
+    }
+
+    /// Check the values against each other; every problem found is reported
+    /// with the TOML key it comes from.
+    pub fn validate(&self) -> Result<(), ParamError> {
+        let mut issues = Vec::new();
+        let mut check = |ok: bool, key: &str, message: String| {
+            if !ok {
+                issues.push(ParamIssue {
+                    key: key.to_string(),
+                    message,
+                });
+            }
+        };
+
+        let dofs = [
+            ("jnt_num", self.jnt_num),
+            ("arm_dof", self.arm_dof),
+            ("finger_dof_left", self.finger_dof_left),
+            ("finger_dof_right", self.finger_dof_right),
+            ("neck_dof", self.neck_dof),
+            ("lumbar_dof", self.lumbar_dof),
+        ];
+        for (key, dof) in dofs {
+            check(dof >= 0, key, format!("is {}, must not be negative", dof));
+        }
+        check(
+            self.arm_dof == 7,
+            "arm_dof",
+            format!("is {}, the arm command has 7 values", self.arm_dof),
+        );
+        let sum = 2 * self.arm_dof as i32 + self.neck_dof as i32 + self.lumbar_dof as i32;
+        check(
+            self.jnt_num as i32 == sum,
+            "jnt_num",
+            format!(
+                "is {}, but 2 * arm_dof + neck_dof + lumbar_dof = {}",
+                self.jnt_num, sum
+            ),
+        );
+        for (key, dof) in [
+            ("finger_dof_left", self.finger_dof_left),
+            ("finger_dof_right", self.finger_dof_right),
+        ] {
+            check(
+                HandType::from_dof(dof).is_some(),
+                key,
+                format!(
+                    "is {}, supported hands have 6 (dexterous) or 1 (gripper)",
+                    dof
+                ),
+            );
+        }
+        let target = self.target_addr.parse::<SocketAddr>();
+        check(
+            target.is_ok(),
+            "target_addr",
+            format!(
+                "`{}` is not an `ip:port` or `[ipv6]:port` address",
+                self.target_addr
+            ),
+        );
+        if let Some(bind) = &self.socket.bind {
+            match bind.parse::<SocketAddr>() {
+                Ok(bind) => check(
+                    target.is_err() || target.is_ok_and(|t| t.is_ipv4() == bind.is_ipv4()),
+                    "socket.bind",
+                    format!("{} is not of the same IP version as target_addr", bind),
+                ),
+                Err(_) => check(
+                    false,
+                    "socket.bind",
+                    format!("`{}` is not an `ip:port` address", bind),
+                ),
+            }
+        }
+        if let Some(dscp) = self.socket.dscp {
+            check(
+                dscp <= 63,
+                "socket.dscp",
+                format!("is {}, must be 0..=63", dscp),
+            );
+        }
+        if let Some(timeout) = self.socket.read_timeout {
+            check(
+                timeout > 0.0,
+                "socket.read_timeout",
+                format!("is {}, must be positive", timeout),
+            );
+        }
+
+        for (key, values, dof) in [
+            ("init.neck", &self.init.neck, self.neck_dof),
+            ("init.lumbar", &self.init.lumbar, self.lumbar_dof),
+        ] {
+            check(
+                values.is_empty() || values.len() as i32 == dof as i32,
+                key,
+                format!("has {} values, expected {}", values.len(), dof),
+            );
+        }
+
+        for (key, [lo, hi]) in [
+            ("neck.yaw_limit", self.neck.yaw_limit),
+            ("neck.pitch_limit", self.neck.pitch_limit),
+        ] {
+            check(
+                lo <= hi,
+                key,
+                format!("lower limit {} above upper {}", lo, hi),
+            );
+        }
+        for i in 0..2 {
+            check(
+                self.neck.max_vel[i] > 0.0,
+                &format!("neck.max_vel[{}]", i),
+                format!("is {}, must be positive", self.neck.max_vel[i]),
+            );
+        }
+        for i in 0..3 {
+            let (lo, hi) = (self.lumbar.min[i], self.lumbar.max[i]);
+            check(
+                lo <= hi,
+                &format!("lumbar.min[{}]", i),
+                format!("{} is above lumbar.max[{}] = {}", lo, i, hi),
+            );
+            check(
+                self.lumbar.max_vel[i] > 0.0,
+                &format!("lumbar.max_vel[{}]", i),
+                format!("is {}, must be positive", self.lumbar.max_vel[i]),
+            );
+        }
+        check(
+            self.lumbar.reach > 0.0,
+            "lumbar.reach",
+            format!("is {}, must be positive", self.lumbar.reach),
+        );
+        check(
+            self.engage.blend_time >= 0.0,
+            "engage.blend_time",
+            format!("is {}, must not be negative", self.engage.blend_time),
+        );
+        for axis in Axis::ALL {
+            let step = self.preset.step.get(axis);
+            check(
+                step > 0.0,
+                &format!("preset.step.{}", axis),
+                format!("is {}, must be positive", step),
+            );
+        }
+        let joystick = &self.joystick;
+        check(
+            (0.0..1.0).contains(&joystick.deadzone),
+            "joystick.deadzone",
+            format!("is {}, must be in [0, 1)", joystick.deadzone),
+        );
+        check(
+            (0.0..=1.0).contains(&joystick.expo),
+            "joystick.expo",
+            format!("is {}, must be in [0, 1]", joystick.expo),
+        );
+        for (table, bindings) in [
+            ("translate", &joystick.translate),
+            ("rotate", &joystick.rotate),
+        ] {
+            for (i, b) in bindings.iter().enumerate() {
+                check(
+                    b.joy < 4,
+                    &format!("joystick.{}[{}].joy", table, i),
+                    format!("is {}, joy has 4 axes", b.joy),
+                );
+            }
+        }
+        let buttons = &joystick.buttons;
+        for (key, bit) in [
+            ("joystick.buttons.switch_arm", buttons.switch_arm),
+            ("joystick.buttons.toggle_hand", buttons.toggle_hand),
+            ("joystick.buttons.switch_mode", buttons.switch_mode),
+        ] {
+            if let Some(bit) = bit {
+                check(bit < 32, key, format!("is {}, key has 32 bits", bit));
+            }
+        }
+        if let Some(watchdog) = &self.watchdog {
+            check(
+                watchdog.timeout > 0.0,
+                "watchdog.timeout",
+                format!("is {}, must be positive", watchdog.timeout),
+            );
+        }
+        let kinematics = &self.kinematics;
+        for (table, chain, dof, dof_key) in [
+            ("left_arm", &kinematics.left_arm, self.arm_dof, "arm_dof"),
+            ("right_arm", &kinematics.right_arm, self.arm_dof, "arm_dof"),
+            ("neck", &kinematics.neck, self.neck_dof, "neck_dof"),
+            ("lumbar", &kinematics.lumbar, self.lumbar_dof, "lumbar_dof"),
+        ] {
+            // a negative dof is reported above
+            let matches = dof < 0 || chain.dof() as i16 == dof;
+            if self.model.is_some() {
+                check(
+                    matches,
+                    "urdf",
+                    format!(
+                        "{} has {} joints, {} is {}",
+                        table,
+                        chain.dof(),
+                        dof_key,
+                        dof
+                    ),
+                );
+            } else {
+                check(
+                    matches,
+                    &format!("kinematics.{}.joints", table),
+                    format!("has {} joints, dof is {}", chain.dof(), dof),
+                );
+            }
+            // NaN limits fail the comparison too
+            for (i, joint) in chain.joints.iter().enumerate() {
+                let (key, message) = match self.model {
+                    Some(_) => (
+                        "urdf".to_string(),
+                        format!(
+                            "joint `{}` lower {} is above upper {}",
+                            joint.name, joint.min, joint.max
+                        ),
+                    ),
+                    None => (
+                        format!("kinematics.{}.joints[{}]", table, i),
+                        format!("min {} is above max {}", joint.min, joint.max),
+                    ),
+                };
+                check(joint.min <= joint.max, &key, message);
+            }
+        }
+        if let Some(model) = &self.model {
+            for (side, dof, dof_key) in [
+                (Side::Left, self.finger_dof_left, "finger_dof_left"),
+                (Side::Right, self.finger_dof_right, "finger_dof_right"),
+            ] {
+                let fingers = model.finger_dof(side);
+                check(
+                    fingers == 0 || fingers as i16 == dof,
+                    "urdf",
+                    format!(
+                        "{} fingers has {} joints, {} is {}",
+                        side, fingers, dof_key, dof
+                    ),
+                );
+            }
+        }
+        for (key, v) in [
+            ("kinematics.drift_position", kinematics.drift_position),
+            ("kinematics.drift_angle", kinematics.drift_angle),
+        ] {
+            check(v > 0.0, key, format!("is {}, must be positive", v));
+        }
+        let ik = &kinematics.ik;
+        check(
+            ik.damping >= 0.0,
+            "kinematics.ik.damping",
+            format!("is {}, must not be negative", ik.damping),
+        );
+        check(
+            ik.max_step > 0.0,
+            "kinematics.ik.max_step",
+            format!("is {}, must be positive", ik.max_step),
+        );
+        for (key, link) in [
+            ("kinematics.ik.shoulder", ik.shoulder),
+            ("kinematics.ik.elbow", ik.elbow),
+            ("kinematics.ik.wrist", ik.wrist),
+        ] {
+            check(
+                link <= kinematics.left_arm.dof(),
+                key,
+                format!(
+                    "is {}, the arm has {} link frames",
+                    link,
+                    kinematics.left_arm.dof() + 1
+                ),
+            );
+        }
+        if let Some(collision) = &self.collision {
+            check(
+                collision.margin >= 0.0,
+                "collision.margin",
+                format!("is {}, must not be negative", collision.margin),
+            );
+            check(
+                collision.samples > 0,
+                "collision.samples",
+                "is 0, must be at least 1".to_string(),
+            );
+            check(
+                collision.slow_zone > collision.margin,
+                "collision.slow_zone",
+                format!(
+                    "is {}, must be larger than collision.margin",
+                    collision.slow_zone
+                ),
+            );
+            for (table, shapes) in [("arm", &collision.arm), ("body", &collision.body)] {
+                for (i, shape) in shapes.iter().enumerate() {
+                    check(
+                        shape.radius() > 0.0,
+                        &format!("collision.{}[{}].radius", table, i),
+                        format!("is {}, must be positive", shape.radius()),
+                    );
+                }
+            }
+        }
+        if let Some(workspace) = &self.workspace {
+            for (i, axis) in ["x", "y", "z"].into_iter().enumerate() {
+                check(
+                    workspace.min[i] <= workspace.max[i],
+                    &format!("workspace.min[{}]", i),
+                    format!(
+                        "{} min {} is above max {}",
+                        axis, workspace.min[i], workspace.max[i]
+                    ),
+                );
+            }
+        }
+        check(
+            self.imu.max_dt > 0.0,
+            "imu.max_dt",
+            format!("is {}, must be positive", self.imu.max_dt),
+        );
+
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(ParamError { issues })
+        }
+    }
+}
+
+/// One problem found by [`LoongManiParam::validate`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParamIssue {
+    /// Dotted TOML key path, e.g. `lumbar.min[1]`.
+    pub key: String,
+    pub message: String,
+}
+
+impl fmt::Display for ParamIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.key, self.message)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ParamError {
+    issues: Vec<ParamIssue>,
+}
+
+impl ParamError {
+    pub fn issues(&self) -> &[ParamIssue] {
+        &self.issues
+    }
+}
+
+impl fmt::Display for ParamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} invalid param value(s)", self.issues.len())?;
+        for issue in &self.issues {
+            write!(f, "\n  {}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParamError {}
+
+const PARAM_ENV: &str = "LOONG_PARAM";
+const PROFILE_ENV: &str = "LOONG_PROFILE";
+const LOCAL_PARAM_PATH: &str = "param/param.toml";
+const PARAM_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/param/param.toml");
+
+// nested tables merge key by key, everything else is replaced
+fn merge(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(b)), toml::Value::Table(o)) => merge(b, o),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
 }
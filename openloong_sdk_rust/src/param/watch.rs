@@ -0,0 +1,65 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::param::LoongManiParam;
+
+/// Polls a param file for changes by modification time.
+#[derive(Clone, Debug)]
+pub struct ParamWatcher {
+    path: PathBuf,
+    profile: Option<String>,
+    interval: Duration,
+    modified: Option<SystemTime>,
+    checked: Option<Instant>,
+}
+
+impl ParamWatcher {
+    /// Watch `path`; the file as it is now counts as already loaded.
+    pub fn new(path: impl AsRef<Path>, profile: Option<&str>) -> Self {
+        let path = path.as_ref().to_path_buf();
+        Self {
+            modified: modified(&path),
+            path,
+            profile: profile.map(str::to_string),
+            interval: Duration::from_millis(500),
+            checked: None,
+        }
+    }
+
+    /// Minimum time between two looks at the file, 0.5 s by default.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn profile(&self) -> Option<&str> {
+        self.profile.as_deref()
+    }
+
+    /// Load the file again if it changed since the last call. `None` when
+    /// nothing changed or the interval has not passed yet.
+    pub fn poll(&mut self) -> Option<Result<LoongManiParam, Box<dyn std::error::Error>>> {
+        let now = Instant::now();
+        if self.checked.is_some_and(|t| now - t < self.interval) {
+            return None;
+        }
+        self.checked = Some(now);
+        let modified = modified(&self.path);
+        if modified == self.modified {
+            return None;
+        }
+        self.modified = modified;
+        Some(LoongManiParam::load_profile(
+            &self.path,
+            self.profile.as_deref(),
+        ))
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
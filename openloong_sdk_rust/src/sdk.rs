@@ -1,57 +1,96 @@
-use ndarray::{Array1, array};
-use std::io::Error;
-use std::net::SocketAddrV4;
-use std::net::UdpSocket;
-use tracing::{debug, error, info};
+use std::io::{Error, ErrorKind};
+use std::net::SocketAddr;
+use tracing::{debug, error};
 
+pub mod arm;
+pub mod builder;
+pub mod collision;
 pub mod ctrl;
+pub mod engage;
+pub mod hand;
+pub mod logger;
+pub mod reload;
+pub mod safety;
 pub mod sens;
+pub mod socket;
+pub mod supervisor;
+pub mod transport;
+pub mod watchdog;
 
-use crate::param::LoongManiParam;
+use crate::estimation::ImuEstimator;
+use crate::param::{LoongManiParam, ParamWatcher};
+use crate::sdk::arm::Side;
+use crate::sdk::builder::LoongManiSdkBuilder;
+use crate::sdk::collision::CollisionChecker;
 use crate::sdk::ctrl::CtrlData;
+use crate::sdk::engage::Engagement;
+use crate::sdk::hand::{GraspPreset, Hand};
+use crate::sdk::logger::PacketLogger;
+use crate::sdk::reload::Injected;
+use crate::sdk::safety::SafetyLimiter;
 use crate::sdk::sens::SensData;
+use crate::sdk::supervisor::{Mode, ModeSupervisor};
+use crate::sdk::transport::Transport;
+use crate::sdk::watchdog::Watchdog;
 
 pub struct LoongManiSdk {
-    socket: UdpSocket,
-    target_addr: SocketAddrV4,
+    transport: Box<dyn Transport>,
     sens: SensData,
+    /// Packets unpacked into `sens` so far.
+    received: u64,
     ctrl: CtrlData,
+    imu: ImuEstimator,
+    hands: [Box<dyn Hand>; 2],
+    supervisor: ModeSupervisor,
+    engagement: Option<Engagement>,
+    injected: Injected,
+    limiter: Option<Box<dyn SafetyLimiter>>,
+    collision: Option<CollisionChecker>,
+    watchdog: Option<Watchdog>,
+    logger: Box<dyn PacketLogger>,
+    param: LoongManiParam,
+    param_watcher: Option<ParamWatcher>,
 }
 
 impl LoongManiSdk {
-    pub fn from_param(param: &LoongManiParam) -> Self {
-        let socket =
-            init_mani_socket("0.0.0.0:0".parse().unwrap()).expect("Failed to initialize socket");
-        debug!(
-            "sdk.socket.ip: {}",
-            socket.local_addr().unwrap().ip().to_string()
-        );
-        debug!(
-            "sdk.socket.port: {}",
-            socket.local_addr().unwrap().port().to_string()
-        );
-        Self {
-            socket,
-            target_addr: param.target_addr().parse().unwrap(),
-            sens: SensData::new(
-                param.jnt_num(),
-                param.finger_dof_left(),
-                param.finger_dof_right(),
-            ),
-            ctrl: CtrlData::new(
-                param.arm_dof(),
-                param.finger_dof_left(),
-                param.finger_dof_right(),
-                param.neck_dof(),
-                param.lumbar_dof(),
-            ),
-        }
+    pub fn builder() -> LoongManiSdkBuilder {
+        LoongManiSdkBuilder::default()
+    }
+
+    /// Build with everything taken from `param`, see [`LoongManiSdk::builder`].
+    pub fn from_param(param: &LoongManiParam) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::builder().param(param.clone()).build().map_err(|e| {
+            error!("Failed to build LoongManiSdk: {}", e);
+            e
+        })
+    }
+
+    pub fn local_addr(&self) -> Result<SocketAddr, Error> {
+        self.transport.local_addr()
+    }
+
+    pub fn watchdog(&self) -> Option<&Watchdog> {
+        self.watchdog.as_ref()
+    }
+
+    pub fn collision(&self) -> Option<&CollisionChecker> {
+        self.collision.as_ref()
+    }
+
+    /// Param currently in effect, including hot reloads.
+    pub fn param(&self) -> &LoongManiParam {
+        &self.param
     }
 
     pub fn sens(&self) -> &SensData {
         &self.sens
     }
 
+    /// Number of packets unpacked into [`LoongManiSdk::sens`] by `recv`.
+    pub fn received(&self) -> u64 {
+        self.received
+    }
+
     pub fn sens_mut(&mut self) -> &mut SensData {
         &mut self.sens
     }
@@ -63,23 +102,125 @@ impl LoongManiSdk {
     pub fn ctrl_mut(&mut self) -> &mut CtrlData {
         &mut self.ctrl
     }
+
+    pub fn imu(&self) -> &ImuEstimator {
+        &self.imu
+    }
+
+    pub fn imu_mut(&mut self) -> &mut ImuEstimator {
+        &mut self.imu
+    }
+
+    pub fn hand(&self, side: Side) -> &dyn Hand {
+        self.hands[side.index()].as_ref()
+    }
+
+    /// Swap in a hand model, e.g. one with a calibrated command range. Fails
+    /// when its DOF differs from the finger DOF of that side.
+    pub fn set_hand(
+        &mut self,
+        side: Side,
+        hand: Box<dyn Hand>,
+    ) -> Result<&mut Self, Box<dyn std::error::Error>> {
+        if hand.dof() != self.ctrl.finger_dof_of(side) as usize {
+            error!("Hand dof does not match finger dof of {} arm", side);
+            return Err(format!("Hand dof does not match finger dof of {} arm", side).into());
+        }
+        self.hands[side.index()] = hand;
+        Ok(self)
+    }
+
+    pub fn set_finger_closure(&mut self, side: Side, percent: f32) -> &mut Self {
+        let cmd = self.hands[side.index()].closure_cmd(percent);
+        self.ctrl.set_finger(side, cmd);
+        self
+    }
+
+    pub fn set_grasp(&mut self, side: Side, preset: GraspPreset) -> &mut Self {
+        let cmd = self.hands[side.index()].preset_cmd(preset);
+        self.ctrl.set_finger(side, cmd);
+        self
+    }
+
+    pub fn supervisor(&self) -> &ModeSupervisor {
+        &self.supervisor
+    }
+
+    pub fn supervisor_mut(&mut self) -> &mut ModeSupervisor {
+        &mut self.supervisor
+    }
+
+    /// Queue a mode change; it is applied over the following `recv` calls.
+    pub fn request_mode(&mut self, mode: Mode) -> Result<(), Box<dyn std::error::Error>> {
+        self.supervisor.request(&self.ctrl, mode)
+    }
+
+    /// Measured closure percentage from `act_finger_*`.
+    pub fn finger_closure(&self, side: Side) -> f32 {
+        let act = match side {
+            Side::Left => &self.sens.act_finger_left,
+            Side::Right => &self.sens.act_finger_right,
+        };
+        self.hands[side.index()].closure_of(act.view())
+    }
 }
 
 impl LoongManiSdk {
+    /// Pack and send the commands, after the safety limiter and collision
+    /// checker if set. Nothing is sent once the watchdog has expired.
     pub fn send(&self) -> Result<(), Error> {
-        let data = self.ctrl.pack_data().unwrap();
-        self.socket.send_to(&data, self.target_addr)?;
-        info!("send data: {}", self.ctrl());
+        if let Some(watchdog) = &self.watchdog
+            && watchdog.expired()
+        {
+            error!(
+                "No SensData for {:?}, not sending",
+                watchdog.age().unwrap_or_default()
+            );
+            return Err(Error::new(ErrorKind::TimedOut, "SensData watchdog expired"));
+        }
+        let (mut limited, mut accepted) = (None, None);
+        if self.limiter.is_some() || self.collision.is_some() {
+            let mut ctrl = self.ctrl.clone();
+            if let Some(limiter) = &self.limiter {
+                limiter.limit(&mut ctrl, &self.sens).map_err(|e| {
+                    error!("Command rejected by safety limiter: {}", e);
+                    Error::other(e.to_string())
+                })?;
+            }
+            if let Some(collision) = &self.collision {
+                accepted = collision.check(&mut ctrl).map_err(|e| {
+                    error!("Command rejected by collision checker: {}", e);
+                    Error::other(e.to_string())
+                })?;
+            }
+            limited = Some(ctrl);
+        }
+        let ctrl = limited.as_ref().unwrap_or(&self.ctrl);
+        let data = ctrl.pack_data().unwrap();
+        self.transport.send(&data)?;
+        // only targets the robot got are a start for the next path check
+        if let (Some(collision), Some(tips)) = (&self.collision, accepted) {
+            collision.commit(tips);
+        }
+        self.logger.sent(ctrl);
         Ok(())
     }
 
     pub fn recv(&mut self) -> Result<(), Error> {
         let mut buf = [0; 2048];
-        if let Ok((size, src_addr)) = self.socket.recv_from(&mut buf) {
-            debug!("src_addr: {}", src_addr.to_string());
+        if let Ok(size) = self.transport.recv(&mut buf) {
             debug!("Received data size: {}", size);
             match self.sens.unpack_data(&buf[..size]) {
-                Ok(_) => {}
+                Ok(_) => {
+                    self.received += 1;
+                    self.logger.received(&self.sens);
+                    if let Some(watchdog) = &mut self.watchdog {
+                        watchdog.feed();
+                    }
+                    self.imu.step(&self.sens);
+                    self.supervisor.step(&mut self.ctrl, &self.sens);
+                    self.step_engage();
+                }
                 Err(e) => {
                     error!("Failed to unpack data: {}", e);
                 }
@@ -87,28 +228,7 @@ impl LoongManiSdk {
         } else {
             error!("Failed to receive data");
         };
+        self.step_param();
         Ok(())
     }
-
-    pub fn pack_data(&mut self, _data: &[u8]) -> Result<(), Error> {
-        let arm_cmd_data = array![
-            [0.4, 0.4, 0.1, 0.0, 0.0, 0.0, 0.5],
-            [0.2, -0.4, 0.1, 0.0, 0.0, 0.0, 0.5]
-        ];
-        let finger_left_data = Array1::zeros(self.ctrl().finger_dof().unwrap() as usize);
-        let finger_right_data = Array1::zeros(self.ctrl().finger_dof().unwrap() as usize);
-
-        self.ctrl_mut()
-            .set_arm_cmd(arm_cmd_data.clone())
-            .set_finger_left(finger_left_data.clone())
-            .set_finger_right(finger_right_data.clone());
-        Ok(())
-    }
-}
-
-fn init_mani_socket(socket_addr_port: SocketAddrV4) -> Result<UdpSocket, Error> {
-    info!("Binding to socket: {:?}", socket_addr_port);
-    let socket = UdpSocket::bind(socket_addr_port)?;
-    socket.set_nonblocking(true).unwrap();
-    Ok(socket)
 }
@@ -0,0 +1,234 @@
+use std::fmt;
+use std::str::FromStr;
+
+use ndarray::{Array1, ArrayView1};
+
+/// Which arm a command or measurement belongs to.
+///
+/// On the wire the left arm is always row 0 and the right arm row 1.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+impl Side {
+    pub const BOTH: [Side; 2] = [Side::Left, Side::Right];
+
+    pub fn index(self) -> usize {
+        match self {
+            Side::Left => 0,
+            Side::Right => 1,
+        }
+    }
+
+    pub fn other(self) -> Side {
+        match self {
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+        }
+    }
+}
+
+impl FromStr for Side {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "left" => Ok(Side::Left),
+            "right" => Ok(Side::Right),
+            _ => Err(format!("Invalid arm string: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for Side {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Side::Left => write!(f, "left"),
+            Side::Right => write!(f, "right"),
+        }
+    }
+}
+
+/// One component of a [`TipPose`], in wire order.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+    Roll,
+    Pitch,
+    Yaw,
+    Elbow,
+}
+
+impl Axis {
+    pub const ALL: [Axis; 7] = [
+        Axis::X,
+        Axis::Y,
+        Axis::Z,
+        Axis::Roll,
+        Axis::Pitch,
+        Axis::Yaw,
+        Axis::Elbow,
+    ];
+
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn is_linear(self) -> bool {
+        matches!(self, Axis::X | Axis::Y | Axis::Z)
+    }
+}
+
+impl fmt::Display for Axis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Axis::X => "x",
+            Axis::Y => "y",
+            Axis::Z => "z",
+            Axis::Roll => "roll",
+            Axis::Pitch => "pitch",
+            Axis::Yaw => "yaw",
+            Axis::Elbow => "elbow",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Cartesian tip target of one arm in the body frame.
+///
+/// Wire order is `[x, y, z, roll, pitch, yaw, elbow_angle]`.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct TipPose {
+    pub xyz: [f32; 3],
+    pub rpy: [f32; 3],
+    pub elbow_angle: f32,
+}
+
+impl TipPose {
+    pub const LEN: usize = 7;
+
+    pub fn new(xyz: [f32; 3], rpy: [f32; 3], elbow_angle: f32) -> Self {
+        Self {
+            xyz,
+            rpy,
+            elbow_angle,
+        }
+    }
+
+    /// Build from a measured `*_tip_p_rpy2b` row, which carries no elbow angle.
+    pub fn from_p_rpy(p_rpy: [f32; 6], elbow_angle: f32) -> Self {
+        Self {
+            xyz: [p_rpy[0], p_rpy[1], p_rpy[2]],
+            rpy: [p_rpy[3], p_rpy[4], p_rpy[5]],
+            elbow_angle,
+        }
+    }
+
+    pub fn from_row(row: ArrayView1<f32>) -> Option<Self> {
+        if row.len() != Self::LEN {
+            return None;
+        }
+        Some(Self {
+            xyz: [row[0], row[1], row[2]],
+            rpy: [row[3], row[4], row[5]],
+            elbow_angle: row[6],
+        })
+    }
+
+    pub fn to_array(&self) -> [f32; 7] {
+        [
+            self.xyz[0],
+            self.xyz[1],
+            self.xyz[2],
+            self.rpy[0],
+            self.rpy[1],
+            self.rpy[2],
+            self.elbow_angle,
+        ]
+    }
+
+    pub fn to_row(&self) -> Array1<f32> {
+        Array1::from_iter(self.to_array())
+    }
+
+    /// Mirror image across the body's x-z plane, i.e. the matching pose
+    /// for the other arm. The elbow angle is kept as is.
+    pub fn mirrored(&self) -> Self {
+        Self {
+            xyz: [self.xyz[0], -self.xyz[1], self.xyz[2]],
+            rpy: [-self.rpy[0], self.rpy[1], -self.rpy[2]],
+            elbow_angle: self.elbow_angle,
+        }
+    }
+
+    pub fn get(&self, axis: Axis) -> f32 {
+        self.to_array()[axis.index()]
+    }
+
+    pub fn set(&mut self, axis: Axis, value: f32) {
+        let mut v = self.to_array();
+        v[axis.index()] = value;
+        *self = Self::from(v);
+    }
+}
+
+impl From<[f32; 7]> for TipPose {
+    fn from(v: [f32; 7]) -> Self {
+        Self {
+            xyz: [v[0], v[1], v[2]],
+            rpy: [v[3], v[4], v[5]],
+            elbow_angle: v[6],
+        }
+    }
+}
+
+impl From<TipPose> for [f32; 7] {
+    fn from(pose: TipPose) -> Self {
+        pose.to_array()
+    }
+}
+
+/// Everything `CtrlData` sends for a single arm: the tip pose target and the
+/// feed-forward force/moment `[fx, fy, fz, mx, my, mz]`.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct ArmCommand {
+    pose: TipPose,
+    fm: [f32; 6],
+}
+
+impl ArmCommand {
+    pub fn new(pose: TipPose) -> Self {
+        Self { pose, fm: [0.0; 6] }
+    }
+
+    pub fn pose(&self) -> &TipPose {
+        &self.pose
+    }
+
+    pub fn pose_mut(&mut self) -> &mut TipPose {
+        &mut self.pose
+    }
+
+    pub fn set_pose(&mut self, pose: TipPose) -> &mut Self {
+        self.pose = pose;
+        self
+    }
+
+    pub fn fm(&self) -> &[f32; 6] {
+        &self.fm
+    }
+
+    pub fn fm_mut(&mut self) -> &mut [f32; 6] {
+        &mut self.fm
+    }
+
+    pub fn set_fm(&mut self, fm: [f32; 6]) -> &mut Self {
+        self.fm = fm;
+        self
+    }
+}
@@ -0,0 +1,197 @@
+use std::net::SocketAddr;
+
+use tracing::{debug, warn};
+
+use crate::estimation::ImuEstimator;
+use crate::param::LoongManiParam;
+use crate::sdk::LoongManiSdk;
+use crate::sdk::arm::Side;
+use crate::sdk::collision::CollisionChecker;
+use crate::sdk::ctrl::CtrlData;
+use crate::sdk::hand::{Hand, HandType};
+use crate::sdk::logger::{PacketLogger, TracingLogger};
+use crate::sdk::reload::Injected;
+use crate::sdk::safety::SafetyLimiter;
+use crate::sdk::sens::SensData;
+use crate::sdk::supervisor::ModeSupervisor;
+use crate::sdk::transport::{Transport, UdpTransport};
+use crate::sdk::watchdog::Watchdog;
+
+/// Assembles a [`LoongManiSdk`]; everything not set comes from the param.
+#[derive(Default)]
+pub struct LoongManiSdkBuilder {
+    param: Option<LoongManiParam>,
+    transport: Option<Box<dyn Transport>>,
+    bind: Option<SocketAddr>,
+    limiter: Option<Box<dyn SafetyLimiter>>,
+    collision: Option<CollisionChecker>,
+    watchdog: Option<Watchdog>,
+    logger: Option<Box<dyn PacketLogger>>,
+    ctrl: Option<CtrlData>,
+}
+
+impl LoongManiSdkBuilder {
+    /// Param to build from, `LoongManiParam::read_from_toml` when unset.
+    pub fn param(mut self, param: LoongManiParam) -> Self {
+        self.param = Some(param);
+        self
+    }
+
+    /// Use this link instead of opening a UDP socket to `target_addr`.
+    pub fn transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.transport = Some(Box::new(transport));
+        self
+    }
+
+    /// Local address of the UDP socket, overrides `socket.bind`.
+    pub fn bind(mut self, addr: SocketAddr) -> Self {
+        self.bind = Some(addr);
+        self
+    }
+
+    /// Overrides the `[workspace]` table.
+    pub fn safety_limiter(mut self, limiter: impl SafetyLimiter + 'static) -> Self {
+        self.limiter = Some(Box::new(limiter));
+        self
+    }
+
+    /// Overrides the `[collision]` table.
+    pub fn collision(mut self, checker: CollisionChecker) -> Self {
+        self.collision = Some(checker);
+        self
+    }
+
+    /// Overrides the `[watchdog]` table.
+    pub fn watchdog(mut self, watchdog: Watchdog) -> Self {
+        self.watchdog = Some(watchdog);
+        self
+    }
+
+    pub fn logger(mut self, logger: impl PacketLogger + 'static) -> Self {
+        self.logger = Some(Box::new(logger));
+        self
+    }
+
+    /// Startup commands, instead of the `[init]` table.
+    pub fn ctrl(mut self, ctrl: CtrlData) -> Self {
+        self.ctrl = Some(ctrl);
+        self
+    }
+
+    pub fn build(self) -> Result<LoongManiSdk, Box<dyn std::error::Error>> {
+        let param = match self.param {
+            Some(param) => {
+                param.validate()?;
+                param
+            }
+            None => LoongManiParam::read_from_toml()?,
+        };
+
+        let transport = match self.transport {
+            Some(transport) => {
+                if self.bind.is_some() {
+                    warn!("Bind address ignored, a transport was given");
+                }
+                transport
+            }
+            None => {
+                let target: SocketAddr = param.target_addr().parse()?;
+                let mut config = param.socket().clone();
+                if let Some(bind) = self.bind {
+                    if bind.is_ipv4() != target.is_ipv4() {
+                        return Err(format!(
+                            "Bind address {} and target {} differ in IP version",
+                            bind, target
+                        )
+                        .into());
+                    }
+                    config.bind = Some(bind.to_string());
+                }
+                Box::new(UdpTransport::open(&config, target)?)
+            }
+        };
+        if let Ok(addr) = transport.local_addr() {
+            debug!("sdk.socket.ip: {}", addr.ip());
+            debug!("sdk.socket.port: {}", addr.port());
+        }
+
+        let ctrl = match self.ctrl {
+            Some(ctrl) => {
+                check_ctrl(&ctrl, &param)?;
+                ctrl
+            }
+            None => CtrlData::from_param(&param)?,
+        };
+
+        Ok(LoongManiSdk {
+            transport,
+            sens: SensData::new(
+                param.jnt_num(),
+                param.finger_dof_left(),
+                param.finger_dof_right(),
+            ),
+            ctrl,
+            imu: ImuEstimator::new(param.imu().clone()),
+            hands: [
+                hand(param.finger_dof_left())?,
+                hand(param.finger_dof_right())?,
+            ],
+            supervisor: ModeSupervisor::default(),
+            received: 0,
+            engagement: None,
+            injected: Injected {
+                limiter: self.limiter.is_some(),
+                collision: self.collision.is_some(),
+                watchdog: self.watchdog.is_some(),
+            },
+            limiter: self.limiter.or_else(|| {
+                param
+                    .workspace()
+                    .map(|limiter| Box::new(limiter.clone()) as Box<dyn SafetyLimiter>)
+            }),
+            collision: self.collision.or_else(|| {
+                param
+                    .collision()
+                    .map(|config| CollisionChecker::new(config.clone()))
+            }),
+            watchdog: self
+                .watchdog
+                .or_else(|| param.watchdog().map(Watchdog::from_config)),
+            logger: self.logger.unwrap_or_else(|| Box::new(TracingLogger)),
+            param_watcher: None,
+            param,
+        })
+    }
+}
+
+fn check_ctrl(ctrl: &CtrlData, param: &LoongManiParam) -> Result<(), Box<dyn std::error::Error>> {
+    let dofs = [
+        ("arm_dof", ctrl.arm_dof(), param.arm_dof()),
+        (
+            "finger_dof_left",
+            ctrl.finger_dof_of(Side::Left),
+            param.finger_dof_left(),
+        ),
+        (
+            "finger_dof_right",
+            ctrl.finger_dof_of(Side::Right),
+            param.finger_dof_right(),
+        ),
+        ("neck_dof", ctrl.neck_dof(), param.neck_dof()),
+        ("lumbar_dof", ctrl.lumbar_dof(), param.lumbar_dof()),
+    ];
+    for (key, got, expected) in dofs {
+        if got != expected {
+            return Err(
+                format!("Initial ctrl has {} = {}, param has {}", key, got, expected).into(),
+            );
+        }
+    }
+    Ok(())
+}
+
+fn hand(dof: i16) -> Result<Box<dyn Hand>, Box<dyn std::error::Error>> {
+    HandType::from_dof(dof)
+        .map(HandType::build)
+        .ok_or_else(|| format!("Unsupported finger dof: {}", dof).into())
+}
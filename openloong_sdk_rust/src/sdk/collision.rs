@@ -0,0 +1,273 @@
+use std::cell::Cell;
+use std::fmt;
+
+use tracing::warn;
+
+use crate::geometry::{Pose, Shape};
+use crate::sdk::arm::{Side, TipPose};
+use crate::sdk::ctrl::{ArmMode, CtrlData};
+
+/// What `send` does with a command that would collide.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CollisionAction {
+    /// Drop the packet, `send` returns an error.
+    #[default]
+    Reject,
+    /// Send the last accepted tip targets again, holding the arms.
+    Stop,
+    /// Go only as far as the path is clear, and take shorter steps once the
+    /// clearance drops below `slow_zone`.
+    Slow,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
+#[serde(default)]
+pub struct CollisionConfig {
+    pub action: CollisionAction,
+    /// Smallest allowed gap between two shapes, meters.
+    pub margin: f64,
+    /// Poses checked on the way from the last accepted command to the new one.
+    pub samples: usize,
+    /// Clearance below which `slow` scales the step down, meters.
+    pub slow_zone: f64,
+    /// Hand and forearm of either arm in the tip frame, the forearm runs
+    /// along -x. Commands carry tip poses only, so these shapes are not
+    /// placed from [`LinkPoses`](crate::kinematics::LinkPoses) even when
+    /// `[kinematics]` or `[urdf]` is set; the upper arm and elbow are not
+    /// modelled.
+    pub arm: Vec<Shape>,
+    /// Torso and head in the body frame.
+    pub body: Vec<Shape>,
+}
+
+impl Default for CollisionConfig {
+    fn default() -> Self {
+        Self {
+            action: CollisionAction::Reject,
+            margin: 0.02,
+            samples: 8,
+            slow_zone: 0.1,
+            arm: vec![
+                Shape::Sphere {
+                    center: [-0.04, 0.0, 0.0],
+                    radius: 0.06,
+                },
+                Shape::Capsule {
+                    a: [-0.1, 0.0, 0.0],
+                    b: [-0.32, 0.0, 0.0],
+                    radius: 0.05,
+                },
+            ],
+            body: vec![
+                Shape::Capsule {
+                    a: [0.0, 0.0, -0.35],
+                    b: [0.0, 0.0, 0.25],
+                    radius: 0.13,
+                },
+                Shape::Sphere {
+                    center: [0.0, 0.0, 0.5],
+                    radius: 0.12,
+                },
+            ],
+        }
+    }
+}
+
+/// One shape of the model, by its index in the config.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Part {
+    Arm(Side, usize),
+    Body(usize),
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::Arm(side, i) => write!(f, "{} arm[{}]", side, i),
+            Part::Body(i) => write!(f, "body[{}]", i),
+        }
+    }
+}
+
+/// Closest pair of shapes and the gap between them, meters.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Contact {
+    pub distance: f64,
+    pub parts: (Part, Part),
+}
+
+impl fmt::Display for Contact {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} and {} {:.3} m apart",
+            self.parts.0, self.parts.1, self.distance
+        )
+    }
+}
+
+/// Checks Cartesian tip targets against each other and the body before they
+/// are sent. The path from the last accepted targets is sampled too, since
+/// the controller sweeps the arms through it.
+#[derive(Debug)]
+pub struct CollisionChecker {
+    config: CollisionConfig,
+    last: Cell<Option<[TipPose; 2]>>,
+}
+
+impl CollisionChecker {
+    pub fn new(config: CollisionConfig) -> Self {
+        Self {
+            config,
+            last: Cell::new(None),
+        }
+    }
+
+    pub fn config(&self) -> &CollisionConfig {
+        &self.config
+    }
+
+    pub fn set_config(&mut self, config: CollisionConfig) {
+        self.config = config;
+    }
+
+    /// Tip targets of the last command let through and sent.
+    pub fn last(&self) -> Option<[TipPose; 2]> {
+        self.last.get()
+    }
+
+    /// Forget the last command, the next one is checked on its own.
+    pub fn reset(&self) {
+        self.last.set(None);
+    }
+
+    /// Closest pair of shapes with the arms at `tips`: arm against arm and
+    /// each arm against the body. `None` without any arm shape.
+    pub fn clearance(&self, tips: &[TipPose; 2]) -> Option<Contact> {
+        let arms = Side::BOTH.map(|side| {
+            let pose = Pose::from_tip(&tips[side.index()]);
+            self.config
+                .arm
+                .iter()
+                .enumerate()
+                .map(|(i, s)| (Part::Arm(side, i), s.transformed(&pose)))
+                .collect::<Vec<_>>()
+        });
+        let body = self
+            .config
+            .body
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (Part::Body(i), *s))
+            .collect::<Vec<_>>();
+
+        let mut closest: Option<Contact> = None;
+        let pairs = [(&arms[0], &arms[1]), (&arms[0], &body), (&arms[1], &body)];
+        for (a, b) in pairs {
+            for (pa, sa) in a {
+                for (pb, sb) in b {
+                    let distance = sa.distance(sb);
+                    if closest.is_none_or(|c| distance < c.distance) {
+                        closest = Some(Contact {
+                            distance,
+                            parts: (*pa, *pb),
+                        });
+                    }
+                }
+            }
+        }
+        closest
+    }
+
+    fn collides(&self, tips: &[TipPose; 2]) -> Option<Contact> {
+        self.clearance(tips)
+            .filter(|c| c.distance < self.config.margin)
+    }
+
+    /// Check the arm targets in `ctrl` and apply the configured action.
+    /// Only `ArmMode::CartesianBodyFrame` is checked. Returns the tip targets
+    /// left in `ctrl`, to be passed to [`CollisionChecker::commit`] once they
+    /// are sent.
+    pub fn check(
+        &self,
+        ctrl: &mut CtrlData,
+    ) -> Result<Option<[TipPose; 2]>, Box<dyn std::error::Error>> {
+        if ctrl.arm_mode() != ArmMode::CartesianBodyFrame {
+            self.reset();
+            return Ok(None);
+        }
+        let target = Side::BOTH.map(|side| *ctrl.arm(side).pose());
+        let last = self.last.get();
+        let samples = self.config.samples.max(1);
+
+        // first colliding pose on the way, with the last free one before it
+        let mut free = last;
+        let mut hit = None;
+        for i in 1..=samples {
+            let t = i as f64 / samples as f64;
+            let tips = match last {
+                Some(last) => interpolate(&last, &target, t),
+                None => target,
+            };
+            if let Some(contact) = self.collides(&tips) {
+                hit = Some(contact);
+                break;
+            }
+            free = Some(tips);
+            if last.is_none() {
+                break;
+            }
+        }
+
+        let Some(contact) = hit else {
+            let mut accepted = target;
+            if self.config.action == CollisionAction::Slow
+                && let Some(last) = last
+                && let Some(c) = self.clearance(&target)
+                && c.distance < self.config.slow_zone
+            {
+                let scale = ((c.distance - self.config.margin)
+                    / (self.config.slow_zone - self.config.margin))
+                    .clamp(0.0, 1.0);
+                accepted = interpolate(&last, &target, scale);
+            }
+            return Ok(Some(accept(ctrl, accepted)));
+        };
+
+        match (self.config.action, last, free) {
+            (CollisionAction::Slow, _, Some(free)) => {
+                warn!("Arm command stopped short, {}", contact);
+                Ok(Some(accept(ctrl, free)))
+            }
+            (CollisionAction::Stop | CollisionAction::Slow, Some(last), _) => {
+                warn!("Arm command held, {}", contact);
+                Ok(Some(accept(ctrl, last)))
+            }
+            _ => Err(format!("Arm command would collide, {}", contact).into()),
+        }
+    }
+
+    /// Record tip targets that reached the robot; the next path is checked
+    /// from them.
+    pub fn commit(&self, tips: [TipPose; 2]) {
+        self.last.set(Some(tips));
+    }
+}
+
+fn accept(ctrl: &mut CtrlData, tips: [TipPose; 2]) -> [TipPose; 2] {
+    for side in Side::BOTH {
+        ctrl.arm_mut(side).set_pose(tips[side.index()]);
+    }
+    tips
+}
+
+fn interpolate(from: &[TipPose; 2], to: &[TipPose; 2], t: f64) -> [TipPose; 2] {
+    Side::BOTH.map(|side| {
+        let (a, b) = (&from[side.index()], &to[side.index()]);
+        let elbow = a.elbow_angle + (b.elbow_angle - a.elbow_angle) * t as f32;
+        Pose::from_tip(a)
+            .interpolate(&Pose::from_tip(b), t)
+            .to_tip(elbow)
+    })
+}
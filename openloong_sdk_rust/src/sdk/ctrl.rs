@@ -1,21 +1,27 @@
 use byteorder::{LittleEndian, WriteBytesExt};
-use log::{error, info};
+use log::{error, info, warn};
 use ndarray::prelude::*;
 use std::io::Error;
 
+use crate::param::LoongManiParam;
+use crate::sdk::arm::{ArmCommand, Side, TipPose};
+use crate::sdk::sens::SensData;
+
 // use crate::param::{
 //     LOONG_ARM_DOF, LOONG_FINGER_DOF_LEFT, LOONG_FINGER_DOF_RIGHT, LOONG_LUMBAR_DOF, LOONG_NECK_DOF,
 // };
 
 #[repr(i16)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum InCharge {
     ManiCtrlDisable,
     ManiCtrlEnable,
 }
 
 #[repr(i16)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum FiltLevel {
     Level0,
     Level1,
@@ -26,8 +32,23 @@ pub enum FiltLevel {
     Disabled,
 }
 
+impl FiltLevel {
+    pub fn from_level(level: i16) -> Option<Self> {
+        match level {
+            0 => Some(FiltLevel::Level0),
+            1 => Some(FiltLevel::Level1),
+            2 => Some(FiltLevel::Level2),
+            3 => Some(FiltLevel::Level3),
+            4 => Some(FiltLevel::Level4),
+            5 => Some(FiltLevel::Level5),
+            _ => None,
+        }
+    }
+}
+
 #[repr(i16)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ArmMode {
     None,
     Reset,
@@ -37,7 +58,8 @@ pub enum ArmMode {
 }
 
 #[repr(i16)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum FingerMode {
     None,
     Reset,
@@ -47,7 +69,8 @@ pub enum FingerMode {
 }
 
 #[repr(i16)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum NeckMode {
     None,
     Reset,
@@ -59,7 +82,8 @@ pub enum NeckMode {
 }
 
 #[repr(i16)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum LumbarMode {
     None,
     Reset,
@@ -68,6 +92,7 @@ pub enum LumbarMode {
     PostCtrl,
 }
 
+#[derive(Clone)]
 pub struct CtrlData {
     in_charge: InCharge,
     filt_level: FiltLevel,
@@ -75,8 +100,7 @@ pub struct CtrlData {
     finger_mode: FingerMode,
     neck_mode: NeckMode,
     lumbar_mode: LumbarMode,
-    arm_cmd: Array2<f32>,
-    arm_fm: Array2<f32>,
+    arms: [ArmCommand; 2],
     finger_left: Array1<f32>,
     finger_right: Array1<f32>,
     neck_cmd: Array1<f32>,
@@ -88,6 +112,57 @@ pub struct CtrlData {
     lumbar_dof: i16,
 }
 
+/// Startup values of `CtrlData`, read from the `[init]` table of the param
+/// file. Empty `neck`/`lumbar` homes mean all zeros.
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(default)]
+pub struct CtrlInit {
+    pub in_charge: InCharge,
+    pub filt_level: FiltLevel,
+    pub arm_mode: ArmMode,
+    pub finger_mode: FingerMode,
+    pub neck_mode: NeckMode,
+    pub lumbar_mode: LumbarMode,
+    pub arm_left: [f32; 7],
+    pub arm_right: [f32; 7],
+    pub neck: Vec<f32>,
+    pub lumbar: Vec<f32>,
+}
+
+impl Default for CtrlInit {
+    fn default() -> Self {
+        Self {
+            in_charge: InCharge::ManiCtrlEnable,
+            filt_level: FiltLevel::Level1,
+            arm_mode: ArmMode::CartesianBodyFrame,
+            finger_mode: FingerMode::JntAxisCtrl,
+            neck_mode: NeckMode::LookLeftHand,
+            lumbar_mode: LumbarMode::None,
+            arm_left: [0.4, 0.3, 0.1, 0.0, 0.0, 0.0, 0.5],
+            arm_right: [0.2, -0.3, 0.1, 0.0, 0.0, 0.0, 0.5],
+            neck: Vec::new(),
+            lumbar: Vec::new(),
+        }
+    }
+}
+
+fn home(values: &[f32], dof: i16, name: &str) -> Result<Array1<f32>, Box<dyn std::error::Error>> {
+    if values.is_empty() {
+        return Ok(Array1::<f32>::zeros(dof as usize));
+    }
+    if values.len() != dof as usize {
+        error!("Invalid {} home", name);
+        return Err(format!(
+            "{} home has {} values, expected {}",
+            name,
+            values.len(),
+            dof
+        )
+        .into());
+    }
+    Ok(Array1::from_vec(values.to_vec()))
+}
+
 impl CtrlData {
     pub fn new(
         arm_dof: i16,
@@ -95,34 +170,67 @@ impl CtrlData {
         finger_dof_right: i16,
         neck_dof: i16,
         lumbar_dof: i16,
-    ) -> Self {
-        let arm_cmd = array![
-            [0.4, 0.3, 0.1, 0.0, 0.0, 0.0, 0.5],
-            [0.2, -0.3, 0.1, 0.0, 0.0, 0.0, 0.5]
-        ];
-        if arm_cmd.shape()[1] != (arm_dof as usize) {
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::with_init(
+            arm_dof,
+            finger_dof_left,
+            finger_dof_right,
+            neck_dof,
+            lumbar_dof,
+            &CtrlInit::default(),
+        )
+    }
+
+    pub fn from_param(param: &LoongManiParam) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::with_init(
+            param.arm_dof(),
+            param.finger_dof_left(),
+            param.finger_dof_right(),
+            param.neck_dof(),
+            param.lumbar_dof(),
+            param.init(),
+        )
+    }
+
+    pub fn with_init(
+        arm_dof: i16,
+        finger_dof_left: i16,
+        finger_dof_right: i16,
+        neck_dof: i16,
+        lumbar_dof: i16,
+        init: &CtrlInit,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        if arm_dof as usize != TipPose::LEN {
             error!("Invalid arm dof");
-            panic!("Invalid arm dof");
+            return Err(format!(
+                "arm_dof is {}, the arm command needs {}",
+                arm_dof,
+                TipPose::LEN
+            )
+            .into());
         }
-        Self {
-            in_charge: InCharge::ManiCtrlEnable,
-            filt_level: FiltLevel::Level1,
-            arm_mode: ArmMode::CartesianBodyFrame,
-            finger_mode: FingerMode::JntAxisCtrl,
-            neck_mode: NeckMode::LookLeftHand,
-            lumbar_mode: LumbarMode::None,
-            arm_cmd: arm_cmd.clone(),
-            arm_fm: Array2::<f32>::zeros((2, 6).f()),
+        let arms = [
+            ArmCommand::new(TipPose::from(init.arm_left)),
+            ArmCommand::new(TipPose::from(init.arm_right)),
+        ];
+        Ok(Self {
+            in_charge: init.in_charge,
+            filt_level: init.filt_level,
+            arm_mode: init.arm_mode,
+            finger_mode: init.finger_mode,
+            neck_mode: init.neck_mode,
+            lumbar_mode: init.lumbar_mode,
+            arms,
             finger_left: Array1::<f32>::zeros(finger_dof_left as usize),
             finger_right: Array1::<f32>::zeros(finger_dof_right as usize),
-            neck_cmd: Array1::<f32>::zeros(neck_dof as usize),
-            lumbar_cmd: Array1::<f32>::zeros(lumbar_dof as usize),
+            neck_cmd: home(&init.neck, neck_dof, "neck")?,
+            lumbar_cmd: home(&init.lumbar, lumbar_dof, "lumbar")?,
             arm_dof,
             finger_dof_left,
             finger_dof_right,
             neck_dof,
             lumbar_dof,
-        }
+        })
     }
 
     // pub fn default_loong_ctrl_data() -> Self {
@@ -160,11 +268,58 @@ impl CtrlData {
         }
         None
     }
+    pub fn finger_dof_of(&self, side: Side) -> i16 {
+        match side {
+            Side::Left => self.finger_dof_left,
+            Side::Right => self.finger_dof_right,
+        }
+    }
+    pub fn finger(&self, side: Side) -> &Array1<f32> {
+        match side {
+            Side::Left => &self.finger_left,
+            Side::Right => &self.finger_right,
+        }
+    }
+    pub fn arm(&self, side: Side) -> &ArmCommand {
+        &self.arms[side.index()]
+    }
+    /// Replaces `arm_cmd_mut`, which handed out the packed `[2, 7]` array;
+    /// use `arm_mut(side).pose_mut()` or [`CtrlData::set_arm_cmd`].
+    #[doc(alias = "arm_cmd_mut")]
+    pub fn arm_mut(&mut self, side: Side) -> &mut ArmCommand {
+        &mut self.arms[side.index()]
+    }
     pub fn arm_cmd(&self) -> Array2<f32> {
-        self.arm_cmd.clone()
+        Array2::from_shape_fn((2, TipPose::LEN), |(i, j)| {
+            self.arms[i].pose().to_array()[j]
+        })
+    }
+    pub fn arm_fm(&self) -> Array2<f32> {
+        Array2::from_shape_fn((2, 6), |(i, j)| self.arms[i].fm()[j])
+    }
+    pub fn in_charge(&self) -> InCharge {
+        self.in_charge
+    }
+    pub fn filt_level(&self) -> FiltLevel {
+        self.filt_level
+    }
+    pub fn arm_mode(&self) -> ArmMode {
+        self.arm_mode
+    }
+    pub fn finger_mode(&self) -> FingerMode {
+        self.finger_mode
+    }
+    pub fn neck_mode(&self) -> NeckMode {
+        self.neck_mode
+    }
+    pub fn lumbar_mode(&self) -> LumbarMode {
+        self.lumbar_mode
     }
-    pub fn arm_cmd_mut(&mut self) -> &mut Array2<f32> {
-        &mut self.arm_cmd
+    pub fn neck_cmd(&self) -> &Array1<f32> {
+        &self.neck_cmd
+    }
+    pub fn lumbar_cmd(&self) -> &Array1<f32> {
+        &self.lumbar_cmd
     }
     pub fn set_in_charge(&mut self, in_charge: InCharge) -> &mut Self {
         self.in_charge = in_charge;
@@ -191,16 +346,27 @@ impl CtrlData {
         self
     }
     pub fn set_arm_cmd(&mut self, arm_cmd: Array2<f32>) -> &mut Self {
-        if arm_cmd.shape()[1] != self.arm_dof as usize {
+        if arm_cmd.shape() != [2, self.arm_dof as usize] {
             error!("Invalid arm dof");
             panic!("Invalid arm dof");
         }
-        self.arm_cmd = arm_cmd.clone();
-        info!("Set arm_cmd: {:?}", self.arm_cmd);
+        for (arm, row) in self.arms.iter_mut().zip(arm_cmd.outer_iter()) {
+            // shape checked above, so every row holds a full pose
+            arm.set_pose(TipPose::from_row(row).unwrap());
+        }
+        info!("Set arm_cmd: {:?}", arm_cmd);
         self
     }
     pub fn set_arm_fm(&mut self, arm_fm: Array2<f32>) -> &mut Self {
-        self.arm_fm = arm_fm.clone();
+        if arm_fm.shape() != [2, 6] {
+            error!("Invalid arm fm shape");
+            panic!("Invalid arm fm shape");
+        }
+        for (arm, row) in self.arms.iter_mut().zip(arm_fm.outer_iter()) {
+            for (dst, &src) in arm.fm_mut().iter_mut().zip(row.iter()) {
+                *dst = src;
+            }
+        }
         self
     }
     pub fn set_finger_left(&mut self, finger_left: Array1<f32>) -> &mut Self {
@@ -219,6 +385,12 @@ impl CtrlData {
         self.finger_right = finger_right.clone();
         self
     }
+    pub fn set_finger(&mut self, side: Side, finger: Array1<f32>) -> &mut Self {
+        match side {
+            Side::Left => self.set_finger_left(finger),
+            Side::Right => self.set_finger_right(finger),
+        }
+    }
     pub fn set_neck_cmd(&mut self, neck_cmd: Array1<f32>) -> &mut Self {
         if neck_cmd.shape()[0] != self.neck_dof as usize {
             error!("Invalid neck dof");
@@ -237,6 +409,100 @@ impl CtrlData {
     }
 }
 
+// Seeding commands from measurements. `act_j` is ordered left arm, right arm,
+// neck, lumbar; in `ArmMode::JntAxisCtrl` the seven `arm_cmd` slots of an arm
+// carry its joint angles instead of a tip pose.
+impl CtrlData {
+    pub fn arm_dof(&self) -> i16 {
+        self.arm_dof
+    }
+    pub fn neck_dof(&self) -> i16 {
+        self.neck_dof
+    }
+    pub fn lumbar_dof(&self) -> i16 {
+        self.lumbar_dof
+    }
+
+    pub fn arm_joints(&self, side: Side) -> [f32; 7] {
+        self.arms[side.index()].pose().to_array()
+    }
+    pub fn set_arm_joints(&mut self, side: Side, joints: [f32; 7]) -> &mut Self {
+        self.arms[side.index()].set_pose(TipPose::from(joints));
+        self
+    }
+
+    fn measured_joints(&self, sens: &SensData, start: usize, len: usize) -> Option<Array1<f32>> {
+        if sens.act_j.len() < start + len {
+            warn!(
+                "act_j holds {} joints, expected at least {}",
+                sens.act_j.len(),
+                start + len
+            );
+            return None;
+        }
+        Some(sens.act_j.slice(s![start..start + len]).to_owned())
+    }
+
+    /// Copy the measured state of one arm into its command, as a tip pose or
+    /// as joint angles depending on `mode`. Returns false if nothing was copied.
+    pub fn seed_arm(&mut self, sens: &SensData, side: Side, mode: ArmMode) -> bool {
+        match mode {
+            ArmMode::CartesianBodyFrame => {
+                let elbow_angle = self.arms[side.index()].pose().elbow_angle;
+                let tip = TipPose::from_p_rpy(sens.act_tip_p_rpy2b[side.index()], elbow_angle);
+                self.arms[side.index()].set_pose(tip);
+                true
+            }
+            ArmMode::JntAxisCtrl => {
+                let dof = self.arm_dof as usize;
+                match self.measured_joints(sens, side.index() * dof, dof) {
+                    Some(q) => {
+                        let joints: [f32; 7] = std::array::from_fn(|i| q[i]);
+                        self.set_arm_joints(side, joints);
+                        true
+                    }
+                    None => false,
+                }
+            }
+            _ => false,
+        }
+    }
+
+    pub fn seed_fingers(&mut self, sens: &SensData) -> bool {
+        if sens.act_finger_left.len() != self.finger_left.len()
+            || sens.act_finger_right.len() != self.finger_right.len()
+        {
+            warn!("Measured finger state does not match finger dof");
+            return false;
+        }
+        self.finger_left = sens.act_finger_left.clone();
+        self.finger_right = sens.act_finger_right.clone();
+        true
+    }
+
+    pub fn seed_neck(&mut self, sens: &SensData) -> bool {
+        let start = 2 * self.arm_dof as usize;
+        match self.measured_joints(sens, start, self.neck_dof as usize) {
+            Some(q) => {
+                self.neck_cmd = q;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn seed_lumbar(&mut self, sens: &SensData) -> bool {
+        let start = 2 * self.arm_dof as usize + self.neck_dof as usize;
+        match self.measured_joints(sens, start, self.lumbar_dof as usize) {
+            Some(q) => {
+                self.lumbar_cmd = q;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
 impl CtrlData {
     pub fn pack_data(&self) -> Result<Vec<u8>, Error> {
         let mut buf = Vec::new();
@@ -248,14 +514,14 @@ impl CtrlData {
         buf.write_i16::<LittleEndian>(self.neck_mode as i16)?;
         buf.write_i16::<LittleEndian>(self.lumbar_mode as i16)?;
 
-        for arm in self.arm_cmd.outer_iter() {
-            for &val in arm.iter() {
+        for arm in &self.arms {
+            for val in arm.pose().to_array() {
                 buf.write_f32::<LittleEndian>(val)?;
             }
         }
 
-        for arm in self.arm_fm.outer_iter() {
-            for &val in arm.iter() {
+        for arm in &self.arms {
+            for &val in arm.fm() {
                 buf.write_f32::<LittleEndian>(val)?;
             }
         }
@@ -289,8 +555,8 @@ impl std::fmt::Display for CtrlData {
         writeln!(f, "finger_mode: {:?}", self.finger_mode)?;
         writeln!(f, "neck_mode: {:?}", self.neck_mode)?;
         writeln!(f, "lumbar_mode: {:?}", self.lumbar_mode)?;
-        writeln!(f, "arm_cmd: {:?}", self.arm_cmd)?;
-        writeln!(f, "arm_fm: {:?}", self.arm_fm)?;
+        writeln!(f, "arm_cmd: {:?}", self.arm_cmd())?;
+        writeln!(f, "arm_fm: {:?}", self.arm_fm())?;
         writeln!(f, "finger_left: {:?}", self.finger_left)?;
         writeln!(f, "finger_right: {:?}", self.finger_right)?;
         writeln!(f, "neck_cmd: {:?}", self.neck_cmd)?;
@@ -0,0 +1,191 @@
+use std::time::Instant;
+
+use tracing::{error, info, warn};
+
+use crate::geometry::Pose;
+use crate::sdk::LoongManiSdk;
+use crate::sdk::arm::{Side, TipPose};
+use crate::sdk::ctrl::{ArmMode, FiltLevel, InCharge};
+use crate::sdk::supervisor::{Mode, Part, Transition, transition};
+
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(default)]
+pub struct EngageConfig {
+    /// Time to blend from the measured pose to the first target, seconds.
+    pub blend_time: f64,
+    /// Filter level right after taking control; ramped down to `filt_end`.
+    pub filt_start: FiltLevel,
+    pub filt_end: FiltLevel,
+}
+
+impl Default for EngageConfig {
+    fn default() -> Self {
+        Self {
+            blend_time: 2.0,
+            filt_start: FiltLevel::Level5,
+            filt_end: FiltLevel::Level1,
+        }
+    }
+}
+
+/// Progress of a handover started by [`LoongManiSdk::engage`].
+#[derive(Clone, Debug)]
+pub struct Engagement {
+    config: EngageConfig,
+    /// Set once the arms are in `cartesian_body_frame`.
+    started: Option<Instant>,
+    start: [TipPose; 2],
+    target: [Option<TipPose>; 2],
+}
+
+impl Engagement {
+    /// Fraction of the blend completed, in `[0, 1]`. Stays at 0 while the
+    /// arms are still on their way into `cartesian_body_frame`.
+    pub fn progress(&self) -> f64 {
+        let Some(started) = self.started else {
+            return 0.0;
+        };
+        if self.config.blend_time <= 0.0 {
+            return 1.0;
+        }
+        (started.elapsed().as_secs_f64() / self.config.blend_time).min(1.0)
+    }
+
+    pub fn is_started(&self) -> bool {
+        self.started.is_some()
+    }
+
+    fn filt_level(&self, t: f64) -> FiltLevel {
+        let (a, b) = (self.config.filt_start, self.config.filt_end);
+        if a == FiltLevel::Disabled || b == FiltLevel::Disabled {
+            return if t < 1.0 { a } else { b };
+        }
+        let level = a as i16 as f64 + (b as i16 - a as i16) as f64 * t;
+        FiltLevel::from_level(level.round() as i16).unwrap_or(b)
+    }
+
+    /// Blended pose, rpy unwrapped towards the `previous` command.
+    fn blend(&self, side: Side, t: f64, previous: &TipPose) -> Option<TipPose> {
+        let start = self.start[side.index()];
+        let target = self.target[side.index()]?;
+        if t >= 1.0 {
+            return Some(target);
+        }
+        // smoothstep, so the arm starts and ends at rest
+        let s = t * t * (3.0 - 2.0 * t);
+        let pose = Pose::from_tip(&start).interpolate(&Pose::from_tip(&target), s);
+        let elbow = start.elbow_angle + (target.elbow_angle - start.elbow_angle) * s as f32;
+        Some(pose.to_tip_near(elbow, previous))
+    }
+}
+
+impl LoongManiSdk {
+    /// Take control without a jump: seed every command from the latest
+    /// `SensData`, enable manipulation control with heavy filtering and start
+    /// ramping towards the targets set with [`LoongManiSdk::engage_to`].
+    /// The arm mode goes through the [`ModeSupervisor`] path, so leaving
+    /// `none` enters `cartesian_body_frame` after a `reset` over the next
+    /// `recv` calls; the blend and filter ramp start from the pose measured
+    /// at that point. Fails until `recv` has unpacked a packet.
+    ///
+    /// [`ModeSupervisor`]: crate::sdk::supervisor::ModeSupervisor
+    pub fn engage(&mut self, config: EngageConfig) -> Result<(), Box<dyn std::error::Error>> {
+        if self.received() == 0 {
+            error!("Cannot engage before any SensData was received");
+            return Err("Cannot engage before any SensData was received".into());
+        }
+        let (ctrl, sens) = (&mut self.ctrl, &self.sens);
+        for side in Side::BOTH {
+            ctrl.seed_arm(sens, side, ArmMode::CartesianBodyFrame);
+        }
+        let seeded = [
+            ctrl.seed_fingers(sens),
+            ctrl.seed_neck(sens),
+            ctrl.seed_lumbar(sens),
+        ];
+        if seeded.contains(&false) {
+            warn!("Engaging without full measured state, some commands keep their values");
+        }
+        ctrl.set_filt_level(config.filt_start)
+            .set_in_charge(InCharge::ManiCtrlEnable);
+        // replace any queued arm transition, entering through Reset when the
+        // supervisor requires it
+        self.supervisor.cancel(Part::Arm);
+        let cartesian = Mode::Arm(ArmMode::CartesianBodyFrame);
+        match transition(Mode::current(ctrl, Part::Arm), cartesian) {
+            Transition::Direct => {
+                ctrl.set_arm_mode(ArmMode::CartesianBodyFrame);
+            }
+            Transition::ViaReset => {
+                if let Err(e) = self.supervisor.request(ctrl, cartesian) {
+                    warn!("Engaging without cartesian_body_frame: {}", e);
+                }
+            }
+        }
+        info!("Engaging over {} s", config.blend_time);
+        let mut engagement = Engagement {
+            config,
+            started: None,
+            start: [TipPose::default(); 2],
+            target: [None, None],
+        };
+        self.start_blend(&mut engagement);
+        self.engagement = Some(engagement);
+        Ok(())
+    }
+
+    /// Start the blend from the measured tip poses once the arms are in
+    /// `cartesian_body_frame`.
+    fn start_blend(&mut self, engagement: &mut Engagement) {
+        if engagement.started.is_some() || self.ctrl.arm_mode() != ArmMode::CartesianBodyFrame {
+            return;
+        }
+        let (ctrl, sens) = (&mut self.ctrl, &self.sens);
+        for side in Side::BOTH {
+            ctrl.seed_arm(sens, side, ArmMode::CartesianBodyFrame);
+        }
+        engagement.start = [*ctrl.arm(Side::Left).pose(), *ctrl.arm(Side::Right).pose()];
+        engagement.started = Some(Instant::now());
+    }
+
+    /// First target of one arm, reached at the end of the engage blend.
+    pub fn engage_to(&mut self, side: Side, target: TipPose) -> &mut Self {
+        match self.engagement.as_mut() {
+            Some(engagement) => engagement.target[side.index()] = Some(target),
+            None => {
+                self.ctrl_mut().arm_mut(side).set_pose(target);
+            }
+        }
+        self
+    }
+
+    pub fn engagement(&self) -> Option<&Engagement> {
+        self.engagement.as_ref()
+    }
+
+    /// Advance the blend and filter ramp; called from `recv`.
+    pub fn step_engage(&mut self) {
+        let Some(mut engagement) = self.engagement.take() else {
+            return;
+        };
+        self.start_blend(&mut engagement);
+        if !engagement.is_started() {
+            self.engagement = Some(engagement);
+            return;
+        }
+        let t = engagement.progress();
+        let level = engagement.filt_level(t);
+        self.ctrl_mut().set_filt_level(level);
+        for side in Side::BOTH {
+            let previous = *self.ctrl().arm(side).pose();
+            if let Some(pose) = engagement.blend(side, t, &previous) {
+                self.ctrl_mut().arm_mut(side).set_pose(pose);
+            }
+        }
+        if t < 1.0 {
+            self.engagement = Some(engagement);
+        } else {
+            info!("Engaged");
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use ndarray::{Array1, ArrayView1};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum HandType {
+    Dexterous6,
+    Gripper1,
+}
+
+impl HandType {
+    pub fn from_dof(dof: i16) -> Option<Self> {
+        match dof {
+            6 => Some(HandType::Dexterous6),
+            1 => Some(HandType::Gripper1),
+            _ => None,
+        }
+    }
+
+    pub fn dof(self) -> usize {
+        match self {
+            HandType::Dexterous6 => 6,
+            HandType::Gripper1 => 1,
+        }
+    }
+
+    pub fn build(self) -> Box<dyn Hand> {
+        match self {
+            HandType::Dexterous6 => Box::new(Dexterous6::default()),
+            HandType::Gripper1 => Box::new(Gripper1::default()),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum GraspPreset {
+    Open,
+    Close,
+    /// Thumb and index meet, the other fingers stay open.
+    Pinch,
+    /// Whole-hand wrap around an object.
+    Power,
+    /// Index extended, the rest curled.
+    Point,
+}
+
+/// Maps normalized closure and grasp presets to the raw finger command of
+/// one hand. Closure is a percentage: 0 fully open, 100 fully closed.
+pub trait Hand: Send {
+    fn hand_type(&self) -> HandType;
+
+    fn dof(&self) -> usize {
+        self.hand_type().dof()
+    }
+
+    fn closure_cmd(&self, percent: f32) -> Array1<f32>;
+
+    fn preset_cmd(&self, preset: GraspPreset) -> Array1<f32>;
+
+    /// Mean closure percentage of a finger command or measurement.
+    fn closure_of(&self, fingers: ArrayView1<f32>) -> f32;
+}
+
+fn lerp(open: f32, closed: f32, percent: f32) -> f32 {
+    open + (closed - open) * percent.clamp(0.0, 100.0) / 100.0
+}
+
+fn percent_of(open: f32, closed: f32, value: f32) -> f32 {
+    if (closed - open).abs() < f32::EPSILON {
+        return 0.0;
+    }
+    ((value - open) / (closed - open) * 100.0).clamp(0.0, 100.0)
+}
+
+/// Six-DOF dexterous hand, joints ordered
+/// `[thumb_rotation, thumb, index, middle, ring, little]`.
+#[derive(Clone, Debug)]
+pub struct Dexterous6 {
+    open: [f32; 6],
+    closed: [f32; 6],
+}
+
+impl Default for Dexterous6 {
+    fn default() -> Self {
+        Self {
+            open: [0.0; 6],
+            closed: [90.0; 6],
+        }
+    }
+}
+
+impl Dexterous6 {
+    pub fn with_range(open: [f32; 6], closed: [f32; 6]) -> Self {
+        Self { open, closed }
+    }
+
+    fn per_finger(&self, percent: [f32; 6]) -> Array1<f32> {
+        Array1::from_iter((0..6).map(|i| lerp(self.open[i], self.closed[i], percent[i])))
+    }
+}
+
+impl Hand for Dexterous6 {
+    fn hand_type(&self) -> HandType {
+        HandType::Dexterous6
+    }
+
+    fn closure_cmd(&self, percent: f32) -> Array1<f32> {
+        self.per_finger([percent; 6])
+    }
+
+    fn preset_cmd(&self, preset: GraspPreset) -> Array1<f32> {
+        let percent = match preset {
+            GraspPreset::Open => [0.0; 6],
+            GraspPreset::Close => [100.0; 6],
+            GraspPreset::Pinch => [100.0, 60.0, 60.0, 0.0, 0.0, 0.0],
+            GraspPreset::Power => [50.0, 80.0, 100.0, 100.0, 100.0, 100.0],
+            GraspPreset::Point => [100.0, 100.0, 0.0, 100.0, 100.0, 100.0],
+        };
+        self.per_finger(percent)
+    }
+
+    fn closure_of(&self, fingers: ArrayView1<f32>) -> f32 {
+        if fingers.len() != 6 {
+            return 0.0;
+        }
+        (0..6)
+            .map(|i| percent_of(self.open[i], self.closed[i], fingers[i]))
+            .sum::<f32>()
+            / 6.0
+    }
+}
+
+/// Single-DOF parallel gripper.
+#[derive(Clone, Debug)]
+pub struct Gripper1 {
+    open: f32,
+    closed: f32,
+}
+
+impl Default for Gripper1 {
+    fn default() -> Self {
+        Self {
+            open: 0.0,
+            closed: 100.0,
+        }
+    }
+}
+
+impl Gripper1 {
+    pub fn with_range(open: f32, closed: f32) -> Self {
+        Self { open, closed }
+    }
+}
+
+impl Hand for Gripper1 {
+    fn hand_type(&self) -> HandType {
+        HandType::Gripper1
+    }
+
+    fn closure_cmd(&self, percent: f32) -> Array1<f32> {
+        Array1::from_elem(1, lerp(self.open, self.closed, percent))
+    }
+
+    fn preset_cmd(&self, preset: GraspPreset) -> Array1<f32> {
+        // a gripper can only approximate the hand shapes
+        let percent = match preset {
+            GraspPreset::Open | GraspPreset::Point => 0.0,
+            GraspPreset::Pinch => 60.0,
+            GraspPreset::Close | GraspPreset::Power => 100.0,
+        };
+        self.closure_cmd(percent)
+    }
+
+    fn closure_of(&self, fingers: ArrayView1<f32>) -> f32 {
+        match fingers.first() {
+            Some(&v) if fingers.len() == 1 => percent_of(self.open, self.closed, v),
+            _ => 0.0,
+        }
+    }
+}
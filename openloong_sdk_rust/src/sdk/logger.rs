@@ -0,0 +1,31 @@
+use tracing::{debug, info};
+
+use crate::sdk::ctrl::CtrlData;
+use crate::sdk::sens::SensData;
+
+/// Sees every packet the SDK sends and every `SensData` it decodes.
+pub trait PacketLogger: Send {
+    fn sent(&self, _ctrl: &CtrlData) {}
+
+    fn received(&self, _sens: &SensData) {}
+}
+
+/// Logs through `tracing`, the default.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TracingLogger;
+
+impl PacketLogger for TracingLogger {
+    fn sent(&self, ctrl: &CtrlData) {
+        info!("send data: {}", ctrl);
+    }
+
+    fn received(&self, sens: &SensData) {
+        debug!("Received data size: {}", sens.data_size);
+    }
+}
+
+/// Logs nothing.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NullLogger;
+
+impl PacketLogger for NullLogger {}
@@ -0,0 +1,141 @@
+use std::time::Duration;
+
+use tracing::{error, info, warn};
+
+use crate::param::{LoongManiParam, ParamWatcher};
+use crate::sdk::LoongManiSdk;
+use crate::sdk::collision::CollisionChecker;
+use crate::sdk::safety::SafetyLimiter;
+use crate::sdk::watchdog::Watchdog;
+
+/// Parts handed to the builder instead of taken from the param; a reload
+/// leaves them as they are.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Injected {
+    pub limiter: bool,
+    pub collision: bool,
+    pub watchdog: bool,
+}
+
+impl LoongManiSdk {
+    /// Check `watcher` from `recv` and apply the file whenever it changes.
+    pub fn watch_param(&mut self, watcher: ParamWatcher) -> &mut Self {
+        info!("Watching {} for param changes", watcher.path().display());
+        self.param_watcher = Some(watcher);
+        self
+    }
+
+    /// Apply the hot-reloadable part of `param`: `init.filt_level`, `[imu]`,
+    /// `[preset]`, `[watchdog]`, `[collision]` and `[workspace]`. A watchdog,
+    /// collision checker or safety limiter given to the builder is kept.
+    /// `[neck]`, `[lumbar]` and `[joystick]` are read back through
+    /// [`LoongManiSdk::param`] by `look_at`, `set_posture`, `reach` and
+    /// `joystick`, which update their controller on the next call. Nothing is applied when `param` is invalid or changes
+    /// the DOFs, the target address, `[socket]`, `[kinematics]` or `[urdf]`,
+    /// which need a restart.
+    pub fn reload_param(
+        &mut self,
+        param: LoongManiParam,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        param.validate()?;
+        let changed = structural_changes(&self.param, &param);
+        if !changed.is_empty() {
+            error!(
+                "Rejected param reload, {} cannot change while running",
+                changed.join(", ")
+            );
+            return Err(format!(
+                "Rejected param reload, {} cannot change while running",
+                changed.join(", ")
+            )
+            .into());
+        }
+
+        let filt_level = param.init().filt_level;
+        if self.engagement.is_some() {
+            warn!("Engaging, filt_level {:?} not applied", filt_level);
+        } else if filt_level != self.param.init().filt_level {
+            self.ctrl.set_filt_level(filt_level);
+        }
+        if param.imu() != self.param.imu() {
+            self.imu.set_config(param.imu().clone());
+        }
+        let injected = self.injected;
+        if injected.watchdog {
+            if param.watchdog() != self.param.watchdog() {
+                warn!("Watchdog set by the builder, [watchdog] not applied");
+            }
+        } else if param.watchdog() != self.param.watchdog() {
+            match (param.watchdog(), &mut self.watchdog) {
+                (Some(config), Some(watchdog)) => {
+                    watchdog.set_timeout(Duration::from_secs_f64(config.timeout))
+                }
+                (Some(config), None) => self.watchdog = Some(Watchdog::from_config(config)),
+                (None, _) => self.watchdog = None,
+            }
+        }
+        if injected.collision {
+            if param.collision() != self.param.collision() {
+                warn!("Collision checker set by the builder, [collision] not applied");
+            }
+        } else if param.collision() != self.param.collision() {
+            match (param.collision(), &mut self.collision) {
+                (Some(config), Some(checker)) => checker.set_config(config.clone()),
+                (Some(config), None) => {
+                    self.collision = Some(CollisionChecker::new(config.clone()))
+                }
+                (None, _) => self.collision = None,
+            }
+        }
+        if injected.limiter {
+            if param.workspace() != self.param.workspace() {
+                warn!("Safety limiter set by the builder, [workspace] not applied");
+            }
+        } else if param.workspace() != self.param.workspace() {
+            self.limiter = param
+                .workspace()
+                .map(|limiter| Box::new(limiter.clone()) as Box<dyn SafetyLimiter>);
+        }
+        self.param = param;
+        info!("Param reloaded");
+        Ok(())
+    }
+
+    /// Poll the watcher set with [`LoongManiSdk::watch_param`]; called from `recv`.
+    pub fn step_param(&mut self) {
+        let Some(result) = self.param_watcher.as_mut().and_then(|w| w.poll()) else {
+            return;
+        };
+        match result {
+            Ok(param) => {
+                // errors are logged by reload_param, the running param stays
+                let _ = self.reload_param(param);
+            }
+            Err(e) => error!("Param reload failed, keeping current values: {}", e),
+        }
+    }
+}
+
+fn structural_changes(old: &LoongManiParam, new: &LoongManiParam) -> Vec<&'static str> {
+    [
+        ("jnt_num", old.jnt_num() != new.jnt_num()),
+        ("arm_dof", old.arm_dof() != new.arm_dof()),
+        (
+            "finger_dof_left",
+            old.finger_dof_left() != new.finger_dof_left(),
+        ),
+        (
+            "finger_dof_right",
+            old.finger_dof_right() != new.finger_dof_right(),
+        ),
+        ("neck_dof", old.neck_dof() != new.neck_dof()),
+        ("lumbar_dof", old.lumbar_dof() != new.lumbar_dof()),
+        ("target_addr", old.target_addr() != new.target_addr()),
+        ("socket", old.socket() != new.socket()),
+        ("kinematics", old.kinematics() != new.kinematics()),
+        ("urdf", old.urdf() != new.urdf()),
+    ]
+    .into_iter()
+    .filter_map(|(key, changed)| changed.then_some(key))
+    .collect()
+}
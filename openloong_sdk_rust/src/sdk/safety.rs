@@ -0,0 +1,42 @@
+use tracing::warn;
+
+use crate::sdk::arm::Side;
+use crate::sdk::ctrl::{ArmMode, CtrlData};
+use crate::sdk::sens::SensData;
+
+/// Last look at the commands before they are packed and sent. Works on a
+/// copy, so limiting never changes what the app set in `CtrlData`; an error
+/// drops the packet.
+pub trait SafetyLimiter: Send {
+    fn limit(&self, ctrl: &mut CtrlData, sens: &SensData)
+    -> Result<(), Box<dyn std::error::Error>>;
+}
+
+/// Keeps Cartesian tip targets inside a body-frame box.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
+pub struct WorkspaceLimiter {
+    /// Lower and upper `[x, y, z]` corners, meters.
+    pub min: [f32; 3],
+    pub max: [f32; 3],
+}
+
+impl SafetyLimiter for WorkspaceLimiter {
+    fn limit(
+        &self,
+        ctrl: &mut CtrlData,
+        _sens: &SensData,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if ctrl.arm_mode() != ArmMode::CartesianBodyFrame {
+            return Ok(());
+        }
+        for side in Side::BOTH {
+            let pose = ctrl.arm_mut(side).pose_mut();
+            let xyz = pose.xyz;
+            pose.xyz = std::array::from_fn(|i| xyz[i].clamp(self.min[i], self.max[i]));
+            if pose.xyz != xyz {
+                warn!("{} tip target {:?} clamped to {:?}", side, xyz, pose.xyz);
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,13 +1,13 @@
 use std::fmt;
-use std::io::{Cursor, Error, Read};
+use std::io::{Cursor, Error, Read, Write};
 use std::mem::size_of;
 
-use byteorder::{LittleEndian, ReadBytesExt};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use ndarray::Array1;
 
 // use crate::param::{LOONG_FINGER_DOF_LEFT, LOONG_FINGER_DOF_RIGHT, LOONG_JNT_NUM};
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct SensData {
     pub data_size: i32,
     pub timestamp: f64,
@@ -202,7 +202,7 @@ impl SensData {
         }
 
         // 7. 解析 act_j
-        let mut act_j = [0.0; 6];
+        let mut act_j = vec![0.0; self.act_j.len()];
         for i in &mut act_j {
             *i = cursor.read_f32::<LittleEndian>()?;
         }
@@ -210,7 +210,7 @@ impl SensData {
         self.act_j = act_j;
 
         // 8. 解析 act_w
-        let mut act_w = [0.0; 6];
+        let mut act_w = vec![0.0; self.act_w.len()];
         for i in &mut act_w {
             *i = cursor.read_f32::<LittleEndian>()?;
         }
@@ -218,7 +218,7 @@ impl SensData {
         self.act_w = act_w;
 
         // 9. 解析 act_t
-        let mut act_t = [0.0; 6];
+        let mut act_t = vec![0.0; self.act_t.len()];
         for i in &mut act_t {
             *i = cursor.read_f32::<LittleEndian>()?;
         }
@@ -226,7 +226,7 @@ impl SensData {
         self.act_t = act_t;
 
         // 10. 解析 drv_temp
-        let mut drv_temp = [0; 6];
+        let mut drv_temp = vec![0; self.drv_temp.len()];
         for i in &mut drv_temp {
             *i = cursor.read_i16::<LittleEndian>()?;
         }
@@ -234,7 +234,7 @@ impl SensData {
         self.drv_temp = drv_temp;
 
         // 11. 解析 drv_state
-        let mut drv_state = [0; 6];
+        let mut drv_state = vec![0; self.drv_state.len()];
         for i in &mut drv_state {
             *i = cursor.read_i16::<LittleEndian>()?;
         }
@@ -242,7 +242,7 @@ impl SensData {
         self.drv_state = drv_state;
 
         // 12. 解析 drv_err
-        let mut drv_err = [0; 6];
+        let mut drv_err = vec![0; self.drv_err.len()];
         for i in &mut drv_err {
             *i = cursor.read_i16::<LittleEndian>()?;
         }
@@ -250,7 +250,7 @@ impl SensData {
         self.drv_err = drv_err;
 
         // 13. 解析 tgt_j
-        let mut tgt_j = [0.0; 6];
+        let mut tgt_j = vec![0.0; self.tgt_j.len()];
         for i in &mut tgt_j {
             *i = cursor.read_f32::<LittleEndian>()?;
         }
@@ -258,7 +258,7 @@ impl SensData {
         self.tgt_j = tgt_j;
 
         // 14. 解析 tgt_w
-        let mut tgt_w = [0.0; 6];
+        let mut tgt_w = vec![0.0; self.tgt_w.len()];
         for i in &mut tgt_w {
             *i = cursor.read_f32::<LittleEndian>()?;
         }
@@ -266,7 +266,7 @@ impl SensData {
         self.tgt_w = tgt_w;
 
         // 15. 解析 tgt_t
-        let mut tgt_t = [0.0; 6];
+        let mut tgt_t = vec![0.0; self.tgt_t.len()];
         for i in &mut tgt_t {
             *i = cursor.read_f32::<LittleEndian>()?;
         }
@@ -274,7 +274,7 @@ impl SensData {
         self.tgt_t = tgt_t;
 
         //解析 act_finger_left
-        let mut act_finger_left = [0.0; 6];
+        let mut act_finger_left = vec![0.0; self.act_finger_left.len()];
         for i in &mut act_finger_left {
             *i = cursor.read_f32::<LittleEndian>()?;
         }
@@ -282,7 +282,7 @@ impl SensData {
         self.act_finger_left = act_finger_left;
 
         //解析 act_finger_right
-        let mut act_finger_right = [0.0; 6];
+        let mut act_finger_right = vec![0.0; self.act_finger_right.len()];
         for i in &mut act_finger_right {
             *i = cursor.read_f32::<LittleEndian>()?;
         }
@@ -290,7 +290,7 @@ impl SensData {
         self.act_finger_right = act_finger_right;
 
         // 解析tgt_finger_left
-        let mut tgt_finger_left = [0.0; 6];
+        let mut tgt_finger_left = vec![0.0; self.tgt_finger_left.len()];
         for i in &mut tgt_finger_left {
             *i = cursor.read_f32::<LittleEndian>()?;
         }
@@ -298,7 +298,7 @@ impl SensData {
         self.tgt_finger_left = tgt_finger_left;
 
         // 解析tgt_finger_right
-        let mut tgt_finger_right = [0.0; 6];
+        let mut tgt_finger_right = vec![0.0; self.tgt_finger_right.len()];
         for i in &mut tgt_finger_right {
             *i = cursor.read_f32::<LittleEndian>()?;
         }
@@ -349,6 +349,70 @@ impl SensData {
 
         Ok(())
     }
+
+    /// Encode in the layout read by [`SensData::unpack_data`], as the robot
+    /// sends it; for simulators and tests.
+    pub fn pack_data(&self) -> Result<Vec<u8>, Error> {
+        let mut buf = Vec::new();
+        buf.write_i32::<LittleEndian>(self.data_size)?;
+        buf.write_f64::<LittleEndian>(self.timestamp)?;
+        for &k in &self.key {
+            buf.write_i16::<LittleEndian>(k)?;
+        }
+        let mut plan_name = [0u8; 16];
+        let name = self.plan_name.as_bytes();
+        let len = name.len().min(plan_name.len());
+        plan_name[..len].copy_from_slice(&name[..len]);
+        buf.write_all(&plan_name)?;
+        for &s in &self.state {
+            buf.write_i16::<LittleEndian>(s)?;
+        }
+        for &v in self
+            .joy
+            .iter()
+            .chain(&self.rpy)
+            .chain(&self.gyr)
+            .chain(&self.acc)
+        {
+            buf.write_f32::<LittleEndian>(v)?;
+        }
+        for joints in [&self.act_j, &self.act_w, &self.act_t] {
+            for &v in joints.iter() {
+                buf.write_f32::<LittleEndian>(v)?;
+            }
+        }
+        for drv in [&self.drv_temp, &self.drv_state, &self.drv_err] {
+            for &v in drv.iter() {
+                buf.write_i16::<LittleEndian>(v)?;
+            }
+        }
+        for values in [
+            &self.tgt_j,
+            &self.tgt_w,
+            &self.tgt_t,
+            &self.act_finger_left,
+            &self.act_finger_right,
+            &self.tgt_finger_left,
+            &self.tgt_finger_right,
+        ] {
+            for &v in values.iter() {
+                buf.write_f32::<LittleEndian>(v)?;
+            }
+        }
+        for tips in [
+            &self.act_tip_p_rpy2b,
+            &self.act_tip_vw2b,
+            &self.act_tip_fm2b,
+            &self.tgt_tip_p_rpy2b,
+            &self.tgt_tip_vw2b,
+            &self.tgt_tip_fm2b,
+        ] {
+            for &v in tips.iter().flatten() {
+                buf.write_f32::<LittleEndian>(v)?;
+            }
+        }
+        Ok(buf)
+    }
 }
 
 impl fmt::Display for SensData {
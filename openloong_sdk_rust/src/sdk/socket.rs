@@ -0,0 +1,72 @@
+use std::io::Error;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket};
+use std::time::Duration;
+
+use socket2::{Domain, Protocol, Socket, Type};
+use tracing::info;
+
+/// Local side of the UDP link, read from the `[socket]` table.
+#[derive(Clone, Debug, Default, PartialEq, serde::Deserialize)]
+#[serde(default)]
+pub struct SocketConfig {
+    /// Local `ip:port` to bind; unset binds any address of the target's
+    /// family on a free port.
+    pub bind: Option<String>,
+    /// `SO_RCVBUF` / `SO_SNDBUF` in bytes, system default when unset.
+    pub recv_buffer: Option<usize>,
+    pub send_buffer: Option<usize>,
+    /// DSCP code point 0..=63 for outgoing packets.
+    pub dscp: Option<u8>,
+    /// Make `recv` block for at most this many seconds; unset keeps the
+    /// socket nonblocking.
+    pub read_timeout: Option<f64>,
+}
+
+impl SocketConfig {
+    /// Address to bind for talking to `target`.
+    pub fn bind_addr(&self, target: SocketAddr) -> Result<SocketAddr, Error> {
+        match &self.bind {
+            Some(bind) => bind
+                .parse()
+                .map_err(|e| Error::other(format!("Invalid bind address {}: {}", bind, e))),
+            None if target.is_ipv6() => Ok((Ipv6Addr::UNSPECIFIED, 0).into()),
+            None => Ok((Ipv4Addr::UNSPECIFIED, 0).into()),
+        }
+    }
+}
+
+pub fn init_mani_socket(config: &SocketConfig, target: SocketAddr) -> Result<UdpSocket, Error> {
+    let bind = config.bind_addr(target)?;
+    info!("Binding to socket: {}", bind);
+    let socket = Socket::new(Domain::for_address(bind), Type::DGRAM, Some(Protocol::UDP))?;
+    if let Some(size) = config.recv_buffer {
+        socket.set_recv_buffer_size(size)?;
+    }
+    if let Some(size) = config.send_buffer {
+        socket.set_send_buffer_size(size)?;
+    }
+    if let Some(dscp) = config.dscp {
+        set_dscp(&socket, bind, dscp)?;
+    }
+    socket.bind(&bind.into())?;
+    match config.read_timeout {
+        Some(timeout) => socket.set_read_timeout(Some(Duration::from_secs_f64(timeout)))?,
+        None => socket.set_nonblocking(true)?,
+    }
+    Ok(socket.into())
+}
+
+// DSCP sits in the upper six bits of the TOS / traffic class byte
+fn set_dscp(socket: &Socket, bind: SocketAddr, dscp: u8) -> Result<(), Error> {
+    let tos = (dscp as u32) << 2;
+    if bind.is_ipv4() {
+        return socket.set_tos(tos);
+    }
+    #[cfg(any(target_os = "linux", target_os = "android", target_os = "macos"))]
+    return socket.set_tclass_v6(tos);
+    #[cfg(not(any(target_os = "linux", target_os = "android", target_os = "macos")))]
+    {
+        tracing::warn!("DSCP marking of IPv6 packets is not supported here");
+        Ok(())
+    }
+}
@@ -0,0 +1,236 @@
+use std::collections::VecDeque;
+
+use tracing::{error, info};
+
+use crate::sdk::arm::Side;
+use crate::sdk::ctrl::{ArmMode, CtrlData, FingerMode, InCharge, LumbarMode, NeckMode};
+use crate::sdk::sens::SensData;
+
+/// What a mode does with the commands in `CtrlData`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Stage {
+    /// Not controlled by the manipulation side.
+    Idle,
+    Reset,
+    Passthrough,
+    /// Follows the command fields, which must hold a sensible target.
+    Tracking,
+    /// Robot-side behavior that ignores the command fields.
+    Autonomous,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Part {
+    Arm,
+    Finger,
+    Neck,
+    Lumbar,
+}
+
+impl Part {
+    pub const ALL: [Part; 4] = [Part::Arm, Part::Finger, Part::Neck, Part::Lumbar];
+
+    fn index(self) -> usize {
+        match self {
+            Part::Arm => 0,
+            Part::Finger => 1,
+            Part::Neck => 2,
+            Part::Lumbar => 3,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Mode {
+    Arm(ArmMode),
+    Finger(FingerMode),
+    Neck(NeckMode),
+    Lumbar(LumbarMode),
+}
+
+impl Mode {
+    pub fn part(self) -> Part {
+        match self {
+            Mode::Arm(_) => Part::Arm,
+            Mode::Finger(_) => Part::Finger,
+            Mode::Neck(_) => Part::Neck,
+            Mode::Lumbar(_) => Part::Lumbar,
+        }
+    }
+
+    pub fn stage(self) -> Stage {
+        match self {
+            Mode::Arm(ArmMode::None)
+            | Mode::Finger(FingerMode::None)
+            | Mode::Neck(NeckMode::None)
+            | Mode::Lumbar(LumbarMode::None) => Stage::Idle,
+            Mode::Arm(ArmMode::Reset)
+            | Mode::Finger(FingerMode::Reset)
+            | Mode::Neck(NeckMode::Reset)
+            | Mode::Lumbar(LumbarMode::Reset) => Stage::Reset,
+            Mode::Arm(ArmMode::LowerLimbCmdPassthrough)
+            | Mode::Finger(FingerMode::LowerLimbCmdPassthrough)
+            | Mode::Neck(NeckMode::LowerLimbCmdPassthrough)
+            | Mode::Lumbar(LumbarMode::LowerLimbCmdPassthrough) => Stage::Passthrough,
+            Mode::Arm(ArmMode::JntAxisCtrl | ArmMode::CartesianBodyFrame)
+            | Mode::Finger(FingerMode::JntAxisCtrl)
+            | Mode::Neck(NeckMode::JntAxisCtrl)
+            | Mode::Lumbar(LumbarMode::JntAxisCtrl | LumbarMode::PostCtrl) => Stage::Tracking,
+            Mode::Finger(FingerMode::Extend)
+            | Mode::Neck(
+                NeckMode::NavigationFollow | NeckMode::LookLeftHand | NeckMode::LookRightHand,
+            ) => Stage::Autonomous,
+        }
+    }
+
+    fn reset(part: Part) -> Mode {
+        match part {
+            Part::Arm => Mode::Arm(ArmMode::Reset),
+            Part::Finger => Mode::Finger(FingerMode::Reset),
+            Part::Neck => Mode::Neck(NeckMode::Reset),
+            Part::Lumbar => Mode::Lumbar(LumbarMode::Reset),
+        }
+    }
+
+    pub fn current(ctrl: &CtrlData, part: Part) -> Mode {
+        match part {
+            Part::Arm => Mode::Arm(ctrl.arm_mode()),
+            Part::Finger => Mode::Finger(ctrl.finger_mode()),
+            Part::Neck => Mode::Neck(ctrl.neck_mode()),
+            Part::Lumbar => Mode::Lumbar(ctrl.lumbar_mode()),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Transition {
+    Direct,
+    ViaReset,
+}
+
+/// Allowed transitions: anything may drop to `Idle` or `Reset`; leaving
+/// `Idle` or `Passthrough` for any other mode goes through `Reset`; from
+/// `Reset`, `Tracking` or `Autonomous` every mode except passthrough is
+/// reachable directly.
+pub fn transition(from: Mode, to: Mode) -> Transition {
+    match (from.stage(), to.stage()) {
+        (_, Stage::Idle | Stage::Reset) => Transition::Direct,
+        (Stage::Reset, _) => Transition::Direct,
+        (Stage::Idle | Stage::Passthrough, _) => Transition::ViaReset,
+        (_, Stage::Passthrough) => Transition::ViaReset,
+        _ => Transition::Direct,
+    }
+}
+
+/// Applies mode requests to `CtrlData` one legal step at a time.
+///
+/// A `Reset` step is held for `reset_ticks` calls to [`ModeSupervisor::step`]
+/// before the next mode is entered. Entering a tracking mode first seeds the
+/// command of that part from the latest `SensData`.
+#[derive(Clone, Debug)]
+pub struct ModeSupervisor {
+    reset_ticks: u32,
+    pending: [VecDeque<Mode>; 4],
+    held: [u32; 4],
+}
+
+impl Default for ModeSupervisor {
+    fn default() -> Self {
+        Self::new(25)
+    }
+}
+
+impl ModeSupervisor {
+    pub fn new(reset_ticks: u32) -> Self {
+        Self {
+            reset_ticks,
+            pending: Default::default(),
+            held: [0; 4],
+        }
+    }
+
+    /// Plan a path to `mode` from the last pending or current mode of its part.
+    pub fn request(
+        &mut self,
+        ctrl: &CtrlData,
+        mode: Mode,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if mode.stage() == Stage::Tracking && ctrl.in_charge() != InCharge::ManiCtrlEnable {
+            error!("Rejected {:?}: manipulation control is disabled", mode);
+            return Err(format!("Rejected {:?}: manipulation control is disabled", mode).into());
+        }
+        let part = mode.part();
+        let queue = &mut self.pending[part.index()];
+        let from = queue.back().copied().unwrap_or(Mode::current(ctrl, part));
+        if from == mode {
+            return Ok(());
+        }
+        if transition(from, mode) == Transition::ViaReset {
+            queue.push_back(Mode::reset(part));
+        }
+        queue.push_back(mode);
+        info!("Mode request {:?}, pending {:?}", mode, queue);
+        Ok(())
+    }
+
+    /// Drop all pending transitions of a part; the current mode stays.
+    pub fn cancel(&mut self, part: Part) {
+        self.pending[part.index()].clear();
+    }
+
+    pub fn pending(&self, part: Part) -> Vec<Mode> {
+        self.pending[part.index()].iter().copied().collect()
+    }
+
+    pub fn is_settled(&self) -> bool {
+        self.pending.iter().all(|q| q.is_empty())
+    }
+
+    pub fn step(&mut self, ctrl: &mut CtrlData, sens: &SensData) {
+        for part in Part::ALL {
+            let i = part.index();
+            let Some(&next) = self.pending[i].front() else {
+                self.held[i] = 0;
+                continue;
+            };
+            let current = Mode::current(ctrl, part);
+            if current.stage() == Stage::Reset && current != next {
+                self.held[i] += 1;
+                if self.held[i] < self.reset_ticks {
+                    continue;
+                }
+            }
+            if next.stage() == Stage::Tracking && !seed(ctrl, sens, next) {
+                error!(
+                    "Cannot seed {:?} from measurements, holding {:?}",
+                    next, current
+                );
+                continue;
+            }
+            apply(ctrl, next);
+            info!("Mode {:?} -> {:?}", current, next);
+            self.pending[i].pop_front();
+            self.held[i] = 0;
+        }
+    }
+}
+
+fn seed(ctrl: &mut CtrlData, sens: &SensData, mode: Mode) -> bool {
+    match mode {
+        Mode::Arm(arm_mode) => Side::BOTH
+            .iter()
+            .all(|&side| ctrl.seed_arm(sens, side, arm_mode)),
+        Mode::Finger(_) => ctrl.seed_fingers(sens),
+        Mode::Neck(_) => ctrl.seed_neck(sens),
+        Mode::Lumbar(_) => ctrl.seed_lumbar(sens),
+    }
+}
+
+fn apply(ctrl: &mut CtrlData, mode: Mode) {
+    match mode {
+        Mode::Arm(m) => ctrl.set_arm_mode(m),
+        Mode::Finger(m) => ctrl.set_finger_mode(m),
+        Mode::Neck(m) => ctrl.set_neck_mode(m),
+        Mode::Lumbar(m) => ctrl.set_lumbar_mode(m),
+    };
+}
@@ -0,0 +1,56 @@
+use std::io::{Error, ErrorKind};
+use std::net::{SocketAddr, UdpSocket};
+
+use crate::sdk::socket::{SocketConfig, init_mani_socket};
+
+/// Datagram link to the robot controller.
+pub trait Transport: Send {
+    fn send(&self, buf: &[u8]) -> Result<usize, Error>;
+
+    /// Next datagram; `WouldBlock` or `TimedOut` when none arrived.
+    fn recv(&self, buf: &mut [u8]) -> Result<usize, Error>;
+
+    fn local_addr(&self) -> Result<SocketAddr, Error> {
+        Err(Error::from(ErrorKind::Unsupported))
+    }
+}
+
+/// UDP socket with a fixed peer, the transport used against the robot.
+#[derive(Debug)]
+pub struct UdpTransport {
+    socket: UdpSocket,
+    target: SocketAddr,
+}
+
+impl UdpTransport {
+    pub fn new(socket: UdpSocket, target: SocketAddr) -> Self {
+        Self { socket, target }
+    }
+
+    pub fn open(config: &SocketConfig, target: SocketAddr) -> Result<Self, Error> {
+        Ok(Self::new(init_mani_socket(config, target)?, target))
+    }
+
+    pub fn socket(&self) -> &UdpSocket {
+        &self.socket
+    }
+
+    pub fn target(&self) -> SocketAddr {
+        self.target
+    }
+}
+
+impl Transport for UdpTransport {
+    fn send(&self, buf: &[u8]) -> Result<usize, Error> {
+        self.socket.send_to(buf, self.target)
+    }
+
+    fn recv(&self, buf: &mut [u8]) -> Result<usize, Error> {
+        let (size, _) = self.socket.recv_from(buf)?;
+        Ok(size)
+    }
+
+    fn local_addr(&self) -> Result<SocketAddr, Error> {
+        self.socket.local_addr()
+    }
+}
@@ -0,0 +1,53 @@
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
+#[serde(default)]
+pub struct WatchdogConfig {
+    /// Longest gap between two `SensData` packets, seconds.
+    pub timeout: f64,
+}
+
+impl Default for WatchdogConfig {
+    fn default() -> Self {
+        Self { timeout: 0.5 }
+    }
+}
+
+/// Stops sending once `SensData` stops arriving. Armed by the first packet,
+/// so commands sent before the robot answers are not held back.
+#[derive(Clone, Debug)]
+pub struct Watchdog {
+    timeout: Duration,
+    fed: Option<Instant>,
+}
+
+impl Watchdog {
+    pub fn new(timeout: Duration) -> Self {
+        Self { timeout, fed: None }
+    }
+
+    pub fn from_config(config: &WatchdogConfig) -> Self {
+        Self::new(Duration::from_secs_f64(config.timeout))
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    pub fn feed(&mut self) {
+        self.fed = Some(Instant::now());
+    }
+
+    /// Time since the last packet, `None` before the first one.
+    pub fn age(&self) -> Option<Duration> {
+        self.fed.map(|t| t.elapsed())
+    }
+
+    pub fn expired(&self) -> bool {
+        self.age().is_some_and(|age| age > self.timeout)
+    }
+}
@@ -0,0 +1,44 @@
+use ndarray::array;
+use openloong_sdk_rust::sdk::arm::{Side, TipPose};
+use openloong_sdk_rust::sdk::ctrl::CtrlData;
+
+#[test]
+fn test_arm_cmd_roundtrip() {
+    let mut ctrl = CtrlData::new(7, 6, 6, 2, 3).unwrap();
+    let arm_cmd = array![
+        [0.4, 0.4, 0.1, 0.0, 0.1, 0.2, 0.5],
+        [0.2, -0.4, 0.1, 0.3, 0.0, 0.0, 0.6]
+    ];
+    ctrl.set_arm_cmd(arm_cmd.clone());
+    assert_eq!(ctrl.arm_cmd(), arm_cmd);
+
+    let left = ctrl.arm(Side::Left).pose();
+    assert_eq!(left.xyz, [0.4, 0.4, 0.1]);
+    assert_eq!(left.rpy, [0.0, 0.1, 0.2]);
+    assert_eq!(left.elbow_angle, 0.5);
+
+    ctrl.arm_mut(Side::Right).pose_mut().xyz[2] += 0.05;
+    assert_eq!(ctrl.arm_cmd()[[1, 2]], 0.1 + 0.05);
+}
+
+#[test]
+fn test_pack_data_layout() {
+    let mut ctrl = CtrlData::new(7, 6, 6, 2, 3).unwrap();
+    ctrl.arm_mut(Side::Left)
+        .set_pose(TipPose::new([1.0, 2.0, 3.0], [4.0, 5.0, 6.0], 7.0))
+        .set_fm([8.0; 6]);
+    let buf = ctrl.pack_data().unwrap();
+    // 6 mode words, 2x7 pose, 2x6 fm, 6 + 6 fingers, 2 neck, 3 lumbar
+    assert_eq!(buf.len(), 6 * 2 + (14 + 12 + 12 + 2 + 3) * 4);
+    let f = |i: usize| f32::from_le_bytes(buf[12 + i * 4..16 + i * 4].try_into().unwrap());
+    assert_eq!(f(0), 1.0);
+    assert_eq!(f(6), 7.0);
+    assert_eq!(f(14), 8.0);
+}
+
+#[test]
+fn test_side_from_str() {
+    assert_eq!("left".parse::<Side>(), Ok(Side::Left));
+    assert_eq!("right".parse::<Side>(), Ok(Side::Right));
+    assert!("middle".parse::<Side>().is_err());
+}
@@ -0,0 +1,112 @@
+use openloong_sdk_rust::app::bimanual::Bimanual;
+use openloong_sdk_rust::geometry::Pose;
+use openloong_sdk_rust::param::LoongManiParam;
+use openloong_sdk_rust::sdk::LoongManiSdk;
+use openloong_sdk_rust::sdk::arm::{Axis, Side, TipPose};
+use openloong_sdk_rust::sdk::ctrl::ArmMode;
+
+fn sdk() -> LoongManiSdk {
+    let param = LoongManiParam::from_toml_str(
+        "jnt_num = 19\narm_dof = 7\nfinger_dof_left = 6\nfinger_dof_right = 6\n\
+         neck_dof = 2\nlumbar_dof = 3\ntarget_addr = \"127.0.0.1:8003\"\n",
+        None,
+    )
+    .unwrap();
+    let mut sdk = LoongManiSdk::from_param(&param).unwrap();
+    sdk.ctrl_mut()
+        .set_arm_mode(ArmMode::CartesianBodyFrame)
+        .arm_mut(Side::Left)
+        .set_pose(TipPose::new([0.4, 0.2, 0.1], [0.1, 0.2, 0.3], 0.5));
+    sdk.ctrl_mut().arm_mut(Side::Right).set_pose(TipPose::new(
+        [0.4, -0.2, 0.1],
+        [-0.1, 0.2, -0.3],
+        0.4,
+    ));
+    sdk
+}
+
+fn assert_near(a: &TipPose, b: &TipPose) {
+    let (a, b) = (a.to_array(), b.to_array());
+    for i in 0..7 {
+        assert!((a[i] - b[i]).abs() < 1e-5, "{a:?} != {b:?}");
+    }
+}
+
+#[test]
+fn test_mirror() {
+    let mut sdk = sdk();
+    sdk.ctrl_mut().arm_mut(Side::Left).set_pose(TipPose::new(
+        [0.5, 0.3, 0.2],
+        [0.3, 0.1, 0.4],
+        0.6,
+    ));
+    sdk.couple(&Bimanual::mirror(Side::Left)).unwrap();
+    let right = *sdk.ctrl().arm(Side::Right).pose();
+    assert_near(
+        &right,
+        &TipPose::new([0.5, -0.3, 0.2], [-0.3, 0.1, -0.4], 0.6),
+    );
+
+    sdk.ctrl_mut().set_arm_mode(ArmMode::JntAxisCtrl);
+    assert!(sdk.couple(&Bimanual::mirror(Side::Left)).is_err());
+}
+
+#[test]
+fn test_leader_follower_keeps_offset() {
+    let mut sdk = sdk();
+    let coupling = Bimanual::leader_follower(sdk.ctrl(), Side::Left);
+    let before = Pose::from_tip(sdk.ctrl().arm(Side::Left).pose())
+        .inverse()
+        .compose(&Pose::from_tip(sdk.ctrl().arm(Side::Right).pose()));
+
+    sdk.jog_in(Side::Left, Axis::Yaw, 0.4, Default::default())
+        .jog_in(Side::Left, Axis::X, 0.1, Default::default());
+    sdk.couple(&coupling).unwrap();
+    let after = Pose::from_tip(sdk.ctrl().arm(Side::Left).pose())
+        .inverse()
+        .compose(&Pose::from_tip(sdk.ctrl().arm(Side::Right).pose()));
+    assert_near(&before.to_tip(0.4), &after.to_tip(0.4));
+    assert_eq!(sdk.ctrl().arm(Side::Right).pose().elbow_angle, 0.4);
+}
+
+#[test]
+fn test_object_frame() {
+    let mut sdk = sdk();
+    let mut coupling = Bimanual::object_between_tips(sdk.ctrl());
+    let object = *coupling.object_pose().unwrap();
+    assert!((object.p[0] - 0.4).abs() < 1e-6 && object.p[1].abs() < 1e-6);
+
+    // unchanged object, bit-identical tips
+    let tips = Side::BOTH.map(|side| *sdk.ctrl().arm(side).pose());
+    sdk.couple(&coupling).unwrap();
+    assert_eq!(Side::BOTH.map(|side| *sdk.ctrl().arm(side).pose()), tips);
+
+    // lift by 0.1 m and turn a quarter about z: the tips swing around the center
+    coupling.move_object(&Pose::from_xyz_rpy(
+        [0.0, 0.0, 0.1],
+        [0.0, 0.0, std::f64::consts::FRAC_PI_2],
+    ));
+    sdk.couple(&coupling).unwrap();
+    let left = sdk.ctrl().arm(Side::Left).pose();
+    let right = sdk.ctrl().arm(Side::Right).pose();
+    assert!((left.xyz[0] - 0.2).abs() < 1e-5 && left.xyz[1].abs() < 1e-5);
+    assert!((right.xyz[0] - 0.6).abs() < 1e-5 && right.xyz[1].abs() < 1e-5);
+    assert!((left.xyz[2] - 0.2).abs() < 1e-5 && (right.xyz[2] - 0.2).abs() < 1e-5);
+    assert_eq!((left.elbow_angle, right.elbow_angle), (0.5, 0.4));
+}
+
+#[test]
+fn test_unmoved_coupling_keeps_roll_pi() {
+    let mut sdk = sdk();
+    let tip = TipPose::new([0.4, 0.2, 0.1], [std::f32::consts::PI, 0.2, 0.3], 0.5);
+    sdk.ctrl_mut().arm_mut(Side::Left).set_pose(tip);
+    sdk.ctrl_mut().arm_mut(Side::Right).set_pose(tip.mirrored());
+    let tips = Side::BOTH.map(|side| *sdk.ctrl().arm(side).pose());
+
+    sdk.couple(&Bimanual::object_between_tips(sdk.ctrl()))
+        .unwrap();
+    assert_eq!(Side::BOTH.map(|side| *sdk.ctrl().arm(side).pose()), tips);
+    sdk.couple(&Bimanual::leader_follower(sdk.ctrl(), Side::Left))
+        .unwrap();
+    assert_eq!(Side::BOTH.map(|side| *sdk.ctrl().arm(side).pose()), tips);
+}
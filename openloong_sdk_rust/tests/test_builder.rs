@@ -0,0 +1,123 @@
+use std::collections::VecDeque;
+use std::io::{Error, ErrorKind};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use openloong_sdk_rust::param::LoongManiParam;
+use openloong_sdk_rust::sdk::LoongManiSdk;
+use openloong_sdk_rust::sdk::arm::Side;
+use openloong_sdk_rust::sdk::ctrl::CtrlData;
+use openloong_sdk_rust::sdk::logger::PacketLogger;
+use openloong_sdk_rust::sdk::safety::WorkspaceLimiter;
+use openloong_sdk_rust::sdk::transport::Transport;
+use openloong_sdk_rust::sdk::watchdog::Watchdog;
+
+#[derive(Clone, Default)]
+struct FakeLink {
+    sent: Arc<Mutex<Vec<Vec<u8>>>>,
+    inbox: Arc<Mutex<VecDeque<Vec<u8>>>>,
+}
+
+impl Transport for FakeLink {
+    fn send(&self, buf: &[u8]) -> Result<usize, Error> {
+        self.sent.lock().unwrap().push(buf.to_vec());
+        Ok(buf.len())
+    }
+
+    fn recv(&self, buf: &mut [u8]) -> Result<usize, Error> {
+        let packet = self
+            .inbox
+            .lock()
+            .unwrap()
+            .pop_front()
+            .ok_or(ErrorKind::WouldBlock)?;
+        buf[..packet.len()].copy_from_slice(&packet);
+        Ok(packet.len())
+    }
+}
+
+#[derive(Clone, Default)]
+struct TipLog(Arc<Mutex<Vec<[f32; 3]>>>);
+
+impl PacketLogger for TipLog {
+    fn sent(&self, ctrl: &CtrlData) {
+        self.0.lock().unwrap().push(ctrl.arm(Side::Left).pose().xyz);
+    }
+}
+
+fn param() -> LoongManiParam {
+    LoongManiParam::from_toml_str(
+        "jnt_num = 19\narm_dof = 7\nfinger_dof_left = 6\nfinger_dof_right = 1\n\
+         neck_dof = 2\nlumbar_dof = 3\ntarget_addr = \"10.0.0.1:8003\"\n",
+        None,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_build_with_fake_transport() {
+    let link = FakeLink::default();
+    let sdk = LoongManiSdk::builder()
+        .param(param())
+        .transport(link.clone())
+        .build()
+        .unwrap();
+    assert!(sdk.local_addr().is_err());
+    assert_eq!(sdk.hand(Side::Right).dof(), 1);
+
+    sdk.send().unwrap();
+    let sent = link.sent.lock().unwrap();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].len(), 12 + (14 + 12 + 7 + 5) * 4);
+}
+
+#[test]
+fn test_safety_limiter_sees_a_copy() {
+    let log = TipLog::default();
+    let mut sdk = LoongManiSdk::builder()
+        .param(param())
+        .transport(FakeLink::default())
+        .safety_limiter(WorkspaceLimiter {
+            min: [0.0, -0.5, -0.3],
+            max: [0.6, 0.5, 0.5],
+        })
+        .logger(log.clone())
+        .build()
+        .unwrap();
+    sdk.ctrl_mut().arm_mut(Side::Left).pose_mut().xyz = [0.9, 0.1, -1.0];
+    sdk.send().unwrap();
+    assert_eq!(log.0.lock().unwrap()[0], [0.6, 0.1, -0.3]);
+    assert_eq!(sdk.ctrl().arm(Side::Left).pose().xyz, [0.9, 0.1, -1.0]);
+}
+
+#[test]
+fn test_watchdog_stops_sending() {
+    let link = FakeLink::default();
+    let mut sdk = LoongManiSdk::builder()
+        .param(param())
+        .transport(link.clone())
+        .watchdog(Watchdog::new(Duration::from_millis(10)))
+        .build()
+        .unwrap();
+    // not armed before the first packet
+    sdk.send().unwrap();
+
+    link.inbox.lock().unwrap().push_back(vec![0; 2048]);
+    sdk.recv().unwrap();
+    sdk.send().unwrap();
+
+    std::thread::sleep(Duration::from_millis(20));
+    let err = sdk.send().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::TimedOut);
+    assert_eq!(link.sent.lock().unwrap().len(), 2);
+}
+
+#[test]
+fn test_build_rejects_mismatched_ctrl() {
+    let result = LoongManiSdk::builder()
+        .param(param())
+        .transport(FakeLink::default())
+        .ctrl(CtrlData::new(7, 6, 6, 2, 3).unwrap())
+        .build();
+    assert!(result.is_err());
+}
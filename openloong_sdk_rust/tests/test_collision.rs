@@ -0,0 +1,167 @@
+use std::io::Error;
+use std::sync::{Arc, Mutex};
+
+use openloong_sdk_rust::geometry::Shape;
+use openloong_sdk_rust::geometry::shape::segment_distance;
+use openloong_sdk_rust::param::LoongManiParam;
+use openloong_sdk_rust::sdk::LoongManiSdk;
+use openloong_sdk_rust::sdk::arm::{Side, TipPose};
+use openloong_sdk_rust::sdk::collision::{CollisionAction, CollisionChecker, CollisionConfig};
+use openloong_sdk_rust::sdk::transport::Transport;
+
+#[derive(Clone, Default)]
+struct CountingLink(Arc<Mutex<usize>>);
+
+impl Transport for CountingLink {
+    fn send(&self, buf: &[u8]) -> Result<usize, Error> {
+        *self.0.lock().unwrap() += 1;
+        Ok(buf.len())
+    }
+
+    fn recv(&self, _buf: &mut [u8]) -> Result<usize, Error> {
+        Err(std::io::ErrorKind::WouldBlock.into())
+    }
+}
+
+const BASE: &str = "jnt_num = 19\narm_dof = 7\nfinger_dof_left = 6\nfinger_dof_right = 6\n\
+                    neck_dof = 2\nlumbar_dof = 3\ntarget_addr = \"127.0.0.1:8003\"\n\
+                    [init]\narm_left = [0.4, 0.3, 0.1, 0.0, 0.0, 0.0, 0.5]\n\
+                    arm_right = [0.4, -0.3, 0.1, 0.0, 0.0, 0.0, 0.5]\n";
+
+fn build(action: CollisionAction) -> (LoongManiSdk, CountingLink) {
+    let link = CountingLink::default();
+    let config = CollisionConfig {
+        action,
+        ..Default::default()
+    };
+    let sdk = LoongManiSdk::builder()
+        .param(LoongManiParam::from_toml_str(BASE, None).unwrap())
+        .transport(link.clone())
+        .collision(CollisionChecker::new(config))
+        .build()
+        .unwrap();
+    (sdk, link)
+}
+
+fn reach_across(sdk: &mut LoongManiSdk) {
+    sdk.ctrl_mut()
+        .arm_mut(Side::Left)
+        .set_pose(TipPose::new([0.4, -0.2, 0.1], [0.0; 3], 0.5));
+}
+
+#[test]
+fn test_segment_distance() {
+    let d = segment_distance([0.0; 3], [1.0, 0.0, 0.0], [0.5, 1.0, -1.0], [0.5, 1.0, 1.0]);
+    assert!((d - 1.0).abs() < 1e-12);
+    // parallel, overlapping in x
+    let d = segment_distance([0.0; 3], [1.0, 0.0, 0.0], [0.5, 0.0, 0.3], [2.0, 0.0, 0.3]);
+    assert!((d - 0.3).abs() < 1e-12);
+    // past the end points
+    let d = segment_distance([0.0; 3], [1.0, 0.0, 0.0], [2.0, 0.0, 0.0], [3.0, 0.0, 0.0]);
+    assert!((d - 1.0).abs() < 1e-12);
+
+    let ball = Shape::Sphere {
+        center: [0.0, 0.0, 1.0],
+        radius: 0.2,
+    };
+    let rod = Shape::Capsule {
+        a: [-1.0, 0.0, 0.0],
+        b: [1.0, 0.0, 0.0],
+        radius: 0.1,
+    };
+    assert!((ball.distance(&rod) - 0.7).abs() < 1e-12);
+}
+
+#[test]
+fn test_collision_param() {
+    let text = format!(
+        "{}[collision]\naction = \"slow\"\nbody = [{{ type = \"sphere\", center = [0.0, 0.0, 0.5], \
+         radius = 0.12 }}]\narm = [{{ type = \"capsule\", a = [0.0, 0.0, 0.0], \
+         b = [-0.3, 0.0, 0.0], radius = -0.05 }}]\n",
+        BASE
+    );
+    let err = LoongManiParam::from_toml_str(&text, None).unwrap_err();
+    let err = err.to_string();
+    assert!(err.contains("collision.arm[0].radius"), "{}", err);
+
+    let param = LoongManiParam::from_toml_str(&text.replace("-0.05", "0.05"), None).unwrap();
+    let config = param.collision().unwrap();
+    assert_eq!(config.action, CollisionAction::Slow);
+    assert_eq!(config.body.len(), 1);
+    assert_eq!(config.margin, 0.02);
+    assert!(
+        LoongManiParam::from_toml_str(BASE, None)
+            .unwrap()
+            .collision()
+            .is_none()
+    );
+}
+
+#[test]
+fn test_reject_and_stop() {
+    let (mut sdk, link) = build(CollisionAction::Reject);
+    sdk.send().unwrap();
+    reach_across(&mut sdk);
+    assert!(sdk.send().is_err());
+    assert_eq!(*link.0.lock().unwrap(), 1);
+    // what the app set is left alone
+    assert_eq!(sdk.ctrl().arm(Side::Left).pose().xyz, [0.4, -0.2, 0.1]);
+
+    let (mut sdk, link) = build(CollisionAction::Stop);
+    sdk.send().unwrap();
+    reach_across(&mut sdk);
+    sdk.send().unwrap();
+    assert_eq!(*link.0.lock().unwrap(), 2);
+    let last = sdk.collision().unwrap().last().unwrap();
+    assert_eq!(last[0].xyz, [0.4, 0.3, 0.1]);
+}
+
+struct DownLink;
+
+impl Transport for DownLink {
+    fn send(&self, _buf: &[u8]) -> Result<usize, Error> {
+        Err(std::io::ErrorKind::ConnectionRefused.into())
+    }
+
+    fn recv(&self, _buf: &mut [u8]) -> Result<usize, Error> {
+        Err(std::io::ErrorKind::WouldBlock.into())
+    }
+}
+
+#[test]
+fn test_failed_send_is_not_accepted() {
+    let sdk = LoongManiSdk::builder()
+        .param(LoongManiParam::from_toml_str(BASE, None).unwrap())
+        .transport(DownLink)
+        .collision(CollisionChecker::new(CollisionConfig::default()))
+        .build()
+        .unwrap();
+    assert!(sdk.send().is_err());
+    assert!(sdk.collision().unwrap().last().is_none());
+}
+
+#[test]
+fn test_slow_stops_short() {
+    let (mut sdk, _link) = build(CollisionAction::Slow);
+    sdk.send().unwrap();
+    reach_across(&mut sdk);
+    sdk.send().unwrap();
+    let checker = sdk.collision().unwrap();
+    let last = checker.last().unwrap();
+    let y = last[0].xyz[1];
+    assert!(y < 0.3 && y > -0.2, "left tip at y = {}", y);
+    let contact = checker.clearance(&last).unwrap();
+    assert!(contact.distance >= checker.config().margin);
+
+    // well clear of everything, a small step goes through unchanged
+    sdk.ctrl_mut()
+        .arm_mut(Side::Left)
+        .set_pose(TipPose::new([0.4, 0.3, 0.1], [0.0; 3], 0.5));
+    sdk.send().unwrap();
+    sdk.ctrl_mut()
+        .arm_mut(Side::Left)
+        .set_pose(TipPose::new([0.45, 0.35, 0.1], [0.0; 3], 0.5));
+    sdk.send().unwrap();
+    let last = sdk.collision().unwrap().last().unwrap();
+    assert!((last[0].xyz[0] - 0.45).abs() < 1e-6);
+}
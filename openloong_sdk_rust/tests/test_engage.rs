@@ -0,0 +1,186 @@
+use std::io::Error;
+use std::sync::{Arc, Mutex};
+
+use openloong_sdk_rust::param::LoongManiParam;
+use openloong_sdk_rust::sdk::LoongManiSdk;
+use openloong_sdk_rust::sdk::arm::{Side, TipPose};
+use openloong_sdk_rust::sdk::ctrl::{ArmMode, FiltLevel, InCharge};
+use openloong_sdk_rust::sdk::engage::EngageConfig;
+use openloong_sdk_rust::sdk::sens::SensData;
+use openloong_sdk_rust::sdk::supervisor::{Mode, Part};
+use openloong_sdk_rust::sdk::transport::Transport;
+
+/// Hands out the same packet on every `recv`.
+#[derive(Clone, Default)]
+struct Feed(Arc<Mutex<Vec<u8>>>);
+
+impl Transport for Feed {
+    fn send(&self, buf: &[u8]) -> Result<usize, Error> {
+        Ok(buf.len())
+    }
+
+    fn recv(&self, buf: &mut [u8]) -> Result<usize, Error> {
+        let packet = self.0.lock().unwrap();
+        buf[..packet.len()].copy_from_slice(&packet);
+        Ok(packet.len())
+    }
+}
+
+fn sdk() -> (LoongManiSdk, Feed) {
+    let param = LoongManiParam::read_from_toml().unwrap();
+    let feed = Feed::default();
+    let sdk = LoongManiSdk::builder()
+        .param(param)
+        .transport(feed.clone())
+        .build()
+        .unwrap();
+    (sdk, feed)
+}
+
+/// Receive the current `SensData` with the left tip measured at `x`.
+fn receive(sdk: &mut LoongManiSdk, feed: &Feed, x: f32) {
+    let mut sens: SensData = sdk.sens().clone();
+    sens.act_tip_p_rpy2b[0] = [x, 0.2, 0.0, 0.0, 0.0, 0.0];
+    sens.act_tip_p_rpy2b[1] = [x, -0.2, 0.0, 0.0, 0.0, 0.0];
+    *feed.0.lock().unwrap() = sens.pack_data().unwrap();
+    sdk.recv().unwrap();
+}
+
+#[test]
+fn test_engage_seeds_then_reaches_target() {
+    let (mut sdk, feed) = sdk();
+    sdk.ctrl_mut().set_in_charge(InCharge::ManiCtrlDisable);
+    receive(&mut sdk, &feed, 0.35);
+
+    sdk.engage(EngageConfig {
+        blend_time: 60.0,
+        ..Default::default()
+    })
+    .unwrap();
+    assert_eq!(sdk.ctrl().in_charge(), InCharge::ManiCtrlEnable);
+    assert_eq!(sdk.ctrl().filt_level(), FiltLevel::Level5);
+    assert_eq!(sdk.ctrl().arm(Side::Left).pose().xyz, [0.35, 0.2, 0.0]);
+
+    let target = TipPose::new([0.4, 0.3, 0.1], [0.0; 3], 0.5);
+    sdk.engage_to(Side::Left, target);
+    sdk.step_engage();
+    // barely started: still at the measured pose
+    let x = sdk.ctrl().arm(Side::Left).pose().xyz[0];
+    assert!((x - 0.35).abs() < 1e-3);
+
+    sdk.engage(EngageConfig {
+        blend_time: 0.0,
+        ..Default::default()
+    })
+    .unwrap();
+    sdk.engage_to(Side::Left, target);
+    sdk.step_engage();
+    assert!(sdk.engagement().is_none());
+    assert_eq!(sdk.ctrl().filt_level(), FiltLevel::Level1);
+    assert_eq!(*sdk.ctrl().arm(Side::Left).pose(), target);
+}
+
+#[test]
+fn test_engage_blend_keeps_roll_pi() {
+    use std::f32::consts::PI;
+    let (mut sdk, feed) = sdk();
+    let mut sens = sdk.sens().clone();
+    sens.act_tip_p_rpy2b[0] = [0.35, 0.2, 0.0, 3.0, 0.0, 0.0];
+    *feed.0.lock().unwrap() = sens.pack_data().unwrap();
+    sdk.recv().unwrap();
+    sdk.engage(EngageConfig {
+        blend_time: 0.05,
+        ..Default::default()
+    })
+    .unwrap();
+    let target = TipPose::new([0.4, 0.3, 0.1], [PI, 0.0, 0.0], 0.5);
+    sdk.engage_to(Side::Left, target);
+    let mut roll = 3.0;
+    while sdk.engagement().is_some() {
+        sdk.step_engage();
+        let next = sdk.ctrl().arm(Side::Left).pose().rpy[0];
+        // heads toward +pi without jumping to the -pi branch
+        assert!(next >= roll - 1e-5, "{roll} -> {next}");
+        roll = next;
+    }
+    assert_eq!(*sdk.ctrl().arm(Side::Left).pose(), target);
+}
+
+#[test]
+fn test_engage_replaces_pending_arm_transition() {
+    let (mut sdk, feed) = sdk();
+    receive(&mut sdk, &feed, 0.35);
+    sdk.ctrl_mut()
+        .set_in_charge(InCharge::ManiCtrlEnable)
+        .set_arm_mode(ArmMode::None);
+    sdk.request_mode(Mode::Arm(ArmMode::JntAxisCtrl)).unwrap();
+
+    // leaving none still goes through reset, the old request is dropped
+    sdk.engage(EngageConfig::default()).unwrap();
+    assert_eq!(sdk.ctrl().arm_mode(), ArmMode::None);
+    let cartesian = Mode::Arm(ArmMode::CartesianBodyFrame);
+    assert_eq!(
+        sdk.supervisor().pending(Part::Arm),
+        vec![Mode::Arm(ArmMode::Reset), cartesian]
+    );
+    let (mut ctrl, sens) = (sdk.ctrl().clone(), sdk.sens());
+    let mut supervisor = sdk.supervisor().clone();
+    for _ in 0..100 {
+        supervisor.step(&mut ctrl, sens);
+    }
+    assert_eq!(ctrl.arm_mode(), ArmMode::CartesianBodyFrame);
+
+    // a direct switch is applied at once
+    sdk.ctrl_mut().set_arm_mode(ArmMode::JntAxisCtrl);
+    sdk.request_mode(Mode::Arm(ArmMode::Reset)).unwrap();
+    sdk.engage(EngageConfig::default()).unwrap();
+    assert_eq!(sdk.ctrl().arm_mode(), ArmMode::CartesianBodyFrame);
+    assert!(sdk.supervisor().is_settled());
+}
+
+#[test]
+fn test_engage_needs_a_packet() {
+    let (mut sdk, feed) = sdk();
+    assert!(sdk.engage(EngageConfig::default()).is_err());
+    assert!(sdk.engagement().is_none());
+    receive(&mut sdk, &feed, 0.35);
+    assert!(sdk.engage(EngageConfig::default()).is_ok());
+}
+
+#[test]
+fn test_engage_blend_starts_after_reset() {
+    let (mut sdk, feed) = sdk();
+    receive(&mut sdk, &feed, 0.35);
+    sdk.ctrl_mut().set_arm_mode(ArmMode::None);
+    sdk.engage(EngageConfig {
+        blend_time: 0.0,
+        ..Default::default()
+    })
+    .unwrap();
+    sdk.engage_to(Side::Left, TipPose::new([0.4, 0.3, 0.1], [0.0; 3], 0.5));
+
+    // the arm moves during reset: neither blend nor filter ramp run yet
+    receive(&mut sdk, &feed, 0.3);
+    assert_eq!(sdk.ctrl().arm_mode(), ArmMode::Reset);
+    assert!(!sdk.engagement().unwrap().is_started());
+    assert_eq!(sdk.ctrl().filt_level(), FiltLevel::Level5);
+    while sdk.ctrl().arm_mode() != ArmMode::CartesianBodyFrame {
+        receive(&mut sdk, &feed, 0.3);
+    }
+    // a zero blend time ends on the same tick it starts
+    assert!(sdk.engagement().is_none());
+    assert_eq!(sdk.ctrl().filt_level(), FiltLevel::Level1);
+
+    // the blend starts from the pose measured after reset
+    sdk.ctrl_mut().set_arm_mode(ArmMode::None);
+    sdk.engage(EngageConfig {
+        blend_time: 60.0,
+        ..Default::default()
+    })
+    .unwrap();
+    while sdk.ctrl().arm_mode() != ArmMode::CartesianBodyFrame {
+        receive(&mut sdk, &feed, 0.25);
+    }
+    assert!(sdk.engagement().unwrap().is_started());
+    assert!((sdk.ctrl().arm(Side::Right).pose().xyz[0] - 0.25).abs() < 1e-6);
+}
@@ -0,0 +1,117 @@
+use std::f64::consts::FRAC_PI_2;
+
+use openloong_sdk_rust::geometry::rotation::matrix_to_rpy;
+use openloong_sdk_rust::geometry::{Frame, FrameTree, Pose, Quat};
+use openloong_sdk_rust::sdk::arm::Side;
+
+fn assert_close(a: &[f64], b: &[f64]) {
+    for (x, y) in a.iter().zip(b) {
+        assert!((x - y).abs() < 1e-9, "{:?} != {:?}", a, b);
+    }
+}
+
+#[test]
+fn test_rpy_quat_matrix_roundtrip() {
+    let rpy = [0.3, -0.4, 1.2];
+    let q = Quat::from_rpy(rpy);
+    assert_close(&q.to_rpy(), &rpy);
+    let m = q.to_matrix();
+    assert_close(&matrix_to_rpy(&m), &rpy);
+    let q2 = Quat::from_matrix(&m);
+    assert!(q.angle_to(&q2) < 1e-9);
+}
+
+#[test]
+fn test_rpy_convention() {
+    // pure yaw of 90 degrees turns x into y
+    let q = Quat::from_rpy([0.0, 0.0, FRAC_PI_2]);
+    assert_close(&q.rotate([1.0, 0.0, 0.0]), &[0.0, 1.0, 0.0]);
+    // roll is applied before yaw (fixed axes x, y, z)
+    let q = Quat::from_rpy([FRAC_PI_2, 0.0, FRAC_PI_2]);
+    assert_close(&q.rotate([0.0, 1.0, 0.0]), &[0.0, 0.0, 1.0]);
+}
+
+#[test]
+fn test_pose_compose_inverse() {
+    let a = Pose::from_xyz_rpy([0.1, 0.2, 0.3], [0.1, 0.2, 0.3]);
+    let b = Pose::from_xyz_rpy([-0.4, 0.0, 0.5], [0.0, -0.7, 1.0]);
+    let ab = a * b;
+    let back = a.inverse() * ab;
+    assert_close(&back.p, &b.p);
+    assert!(back.q.angle_to(&b.q) < 1e-9);
+    let id = ab * ab.inverse();
+    assert_close(&id.p, &[0.0; 3]);
+}
+
+#[test]
+fn test_slerp_midpoint() {
+    let a = Quat::IDENTITY;
+    let b = Quat::from_rpy([0.0, 0.0, 1.0]);
+    let mid = a.slerp(&b, 0.5);
+    assert_close(&mid.to_rpy(), &[0.0, 0.0, 0.5]);
+}
+
+#[test]
+fn test_frame_tree_express() {
+    let mut tree = FrameTree::new();
+    tree.set(
+        Frame::Camera,
+        Frame::Body,
+        Pose::from_xyz_rpy([0.1, 0.0, 0.5], [0.0, FRAC_PI_2, 0.0]),
+    )
+    .unwrap();
+    // a point 1 m along the camera z axis
+    let target = Pose::from_xyz_rpy([0.0, 0.0, 1.0], [0.0; 3]);
+    let in_body = tree.to_body(&target, Frame::Camera);
+    assert_close(&in_body.p, &[1.1, 0.0, 0.5]);
+    let back = tree.express(&in_body, Frame::Body, Frame::Camera);
+    assert_close(&back.p, &target.p);
+
+    assert!(
+        tree.set(Frame::Body, Frame::Tool(Side::Left), Pose::IDENTITY)
+            .is_err()
+    );
+}
+
+#[test]
+fn test_gravity_frame_keeps_level_target() {
+    use openloong_sdk_rust::geometry::GravityFrame;
+    use openloong_sdk_rust::sdk::sens::SensData;
+
+    let mut sens = SensData::new(19, 6, 6);
+    // torso pitched forward by 0.2 rad, heading is ignored
+    sens.rpy = [0.0, 0.2, 1.0];
+    let mut gravity = GravityFrame::new();
+    gravity.update(&sens);
+    assert!((gravity.tilt() - 0.2).abs() < 1e-6);
+
+    // a level cup expressed in the body frame must counter the pitch
+    let level = Pose::from_xyz_rpy([0.4, 0.2, 0.1], [0.0; 3]);
+    let tip = gravity.command(&level, 0.5);
+    assert!((tip.rpy[1] + 0.2).abs() < 1e-6);
+    let back = gravity.to_level(&Pose::from_tip(&tip));
+    for (a, b) in back.p.iter().zip(level.p) {
+        assert!((a - b).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn test_unwrap_rpy_follows_previous() {
+    use openloong_sdk_rust::geometry::rotation::unwrap_rpy;
+    use std::f64::consts::PI;
+    let close = |a: [f64; 3], b: [f64; 3]| a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-9);
+
+    // same rotation, other branch of roll
+    assert!(close(
+        unwrap_rpy([-PI, 0.2, 0.3], [PI, 0.2, 0.3]),
+        [PI, 0.2, 0.3]
+    ));
+    // other solution of the pair: (r + pi, pi - p, y + pi)
+    let other = [0.1 + PI, PI - 0.2, 0.3 - PI];
+    assert!(close(unwrap_rpy(other, [0.1, 0.2, 0.3]), [0.1, 0.2, 0.3]));
+    // at pitch +pi/2 only roll - yaw is observable, yaw is kept
+    assert!(close(
+        unwrap_rpy([0.5, FRAC_PI_2, 0.0], [0.0, FRAC_PI_2, 0.2]),
+        [0.7, FRAC_PI_2, 0.2]
+    ));
+}
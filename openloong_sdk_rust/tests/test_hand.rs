@@ -0,0 +1,65 @@
+use openloong_sdk_rust::param::LoongManiParam;
+use openloong_sdk_rust::sdk::LoongManiSdk;
+use openloong_sdk_rust::sdk::arm::Side;
+use openloong_sdk_rust::sdk::ctrl::CtrlData;
+use openloong_sdk_rust::sdk::hand::{Dexterous6, GraspPreset, HandType};
+
+#[test]
+fn test_hand_type_from_dof() {
+    assert_eq!(HandType::from_dof(6), Some(HandType::Dexterous6));
+    assert_eq!(HandType::from_dof(1), Some(HandType::Gripper1));
+    assert_eq!(HandType::from_dof(7), None);
+}
+
+#[test]
+fn test_closure_roundtrip() {
+    for hand_type in [HandType::Dexterous6, HandType::Gripper1] {
+        let hand = hand_type.build();
+        let cmd = hand.closure_cmd(40.0);
+        assert_eq!(cmd.len(), hand.dof());
+        assert!((hand.closure_of(cmd.view()) - 40.0).abs() < 1e-4);
+        assert_eq!(hand.closure_of(hand.closure_cmd(150.0).view()), 100.0);
+    }
+}
+
+#[test]
+fn test_presets() {
+    let hand = HandType::Dexterous6.build();
+    let point = hand.preset_cmd(GraspPreset::Point);
+    assert_eq!(point[2], 0.0);
+    assert!(point[3] > 0.0);
+    let gripper = HandType::Gripper1.build();
+    assert_eq!(gripper.preset_cmd(GraspPreset::Power).len(), 1);
+}
+
+#[test]
+fn test_mixed_hands_in_ctrl() {
+    let mut ctrl = CtrlData::new(7, 6, 1, 2, 3).unwrap();
+    assert_eq!(ctrl.finger_dof(), None);
+    let left = HandType::Dexterous6.build();
+    let right = HandType::Gripper1.build();
+    ctrl.set_finger(Side::Left, left.closure_cmd(50.0))
+        .set_finger(Side::Right, right.closure_cmd(50.0));
+    assert_eq!(ctrl.finger(Side::Right)[0], 50.0);
+    // 6 mode words, 2x7 pose, 2x6 fm, 6 + 1 fingers, 2 neck, 3 lumbar
+    assert_eq!(ctrl.pack_data().unwrap().len(), 12 + (14 + 12 + 7 + 5) * 4);
+}
+
+#[test]
+fn test_set_hand_checks_dof() {
+    let param = LoongManiParam::from_toml_str(
+        "jnt_num = 19\narm_dof = 7\nfinger_dof_left = 6\nfinger_dof_right = 1\n\
+         neck_dof = 2\nlumbar_dof = 3\ntarget_addr = \"127.0.0.1:8003\"\n",
+        None,
+    )
+    .unwrap();
+    let mut sdk = LoongManiSdk::from_param(&param).unwrap();
+    let calibrated = Dexterous6::with_range([5.0; 6], [80.0; 6]);
+    assert!(
+        sdk.set_hand(Side::Right, Box::new(calibrated.clone()))
+            .is_err()
+    );
+    assert_eq!(sdk.hand(Side::Right).dof(), 1);
+    sdk.set_hand(Side::Left, Box::new(calibrated)).unwrap();
+    assert_eq!(sdk.hand(Side::Left).closure_cmd(0.0)[0], 5.0);
+}
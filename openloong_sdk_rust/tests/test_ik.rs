@@ -0,0 +1,98 @@
+use openloong_sdk_rust::geometry::Pose;
+use openloong_sdk_rust::kinematics::{IkConfig, KinematicsConfig};
+use openloong_sdk_rust::param::LoongManiParam;
+use openloong_sdk_rust::sdk::LoongManiSdk;
+use openloong_sdk_rust::sdk::arm::Side;
+use openloong_sdk_rust::sdk::ctrl::ArmMode;
+
+const Q: [f64; 7] = [0.3, 0.4, -0.2, -1.0, 0.5, 0.2, -0.3];
+const SEED: [f64; 7] = [0.0, 0.2, 0.0, -0.6, 0.0, 0.0, 0.0];
+
+fn assert_pose_near(a: &Pose, b: &Pose) {
+    for i in 0..3 {
+        assert!((a.p[i] - b.p[i]).abs() < 1e-3, "{:?} != {:?}", a, b);
+    }
+    assert!(a.q.angle_to(&b.q) < 1e-2, "{:?} != {:?}", a, b);
+}
+
+#[test]
+fn test_round_trip() {
+    let config = KinematicsConfig::default();
+    let ik = IkConfig::default();
+    for side in Side::BOTH {
+        let chain = config.arm(side);
+        let target = chain.tip(&Pose::IDENTITY, &Q);
+        let psi = ik.arm_angle(chain, &Pose::IDENTITY, &Q, side);
+        let solution = ik.solve(chain, &Pose::IDENTITY, &target, psi, side, &SEED);
+        assert!(solution.converged, "{:?}", solution);
+
+        let tip = chain.tip(&Pose::IDENTITY, &solution.joints);
+        assert_pose_near(&tip, &target);
+        let got = ik.arm_angle(chain, &Pose::IDENTITY, &solution.joints, side);
+        assert!((got - psi).abs() < ik.arm_angle_tolerance);
+    }
+}
+
+#[test]
+fn test_arm_angle_moves_elbow_only() {
+    let config = KinematicsConfig::default();
+    let ik = IkConfig::default();
+    let chain = config.arm(Side::Left);
+    let target = chain.tip(&Pose::IDENTITY, &Q);
+    let psi = ik.arm_angle(chain, &Pose::IDENTITY, &Q, Side::Left);
+
+    let low = ik.solve(chain, &Pose::IDENTITY, &target, psi - 0.3, Side::Left, &Q);
+    let high = ik.solve(chain, &Pose::IDENTITY, &target, psi + 0.3, Side::Left, &Q);
+    assert!(low.converged && high.converged, "{:?} {:?}", low, high);
+    let elbow = |q: &[f64]| chain.forward(&Pose::IDENTITY, q)[3].p;
+    let (a, b) = (elbow(&low.joints), elbow(&high.joints));
+    // a larger arm angle swings the left elbow outwards, to +y
+    assert!(b[1] > a[1] + 0.05, "{:?} {:?}", a, b);
+    assert_pose_near(&chain.tip(&Pose::IDENTITY, &low.joints), &target);
+    assert_pose_near(&chain.tip(&Pose::IDENTITY, &high.joints), &target);
+}
+
+#[test]
+fn test_limits_and_unreachable() {
+    let config = KinematicsConfig::default();
+    let ik = IkConfig::default();
+    let chain = config.arm(Side::Right);
+    let target = Pose::from_xyz_rpy([1.5, -0.2, 0.1], [0.0; 3]);
+    let solution = ik.solve(chain, &Pose::IDENTITY, &target, 0.0, Side::Right, &SEED);
+    assert!(!solution.converged);
+    assert_eq!(solution.iterations, ik.max_iterations);
+    for (q, joint) in solution.joints.iter().zip(&chain.joints) {
+        assert!(
+            *q >= joint.min && *q <= joint.max,
+            "{} out of limits",
+            joint.name
+        );
+    }
+}
+
+#[test]
+fn test_set_tip_ik() {
+    let param = LoongManiParam::from_toml_str(
+        "jnt_num = 19\narm_dof = 7\nfinger_dof_left = 6\nfinger_dof_right = 6\n\
+         neck_dof = 2\nlumbar_dof = 3\ntarget_addr = \"127.0.0.1:8003\"\n",
+        None,
+    )
+    .unwrap();
+    let mut sdk = LoongManiSdk::from_param(&param).unwrap();
+    let chain = param.kinematics().arm(Side::Left);
+    let ik = &param.kinematics().ik;
+    let psi = ik.arm_angle(chain, &Pose::IDENTITY, &Q, Side::Left);
+    let target = chain.tip(&Pose::IDENTITY, &Q).to_tip(psi as f32);
+    assert!(sdk.set_tip_ik(Side::Left, &target).is_err());
+
+    sdk.ctrl_mut()
+        .set_arm_mode(ArmMode::JntAxisCtrl)
+        .set_arm_joints(Side::Left, SEED.map(|v| v as f32));
+    let solution = sdk.set_tip_ik(Side::Left, &target).unwrap();
+    let joints = sdk.ctrl().arm_joints(Side::Left);
+    for (got, expected) in joints.iter().zip(&solution.joints) {
+        assert_eq!(*got, *expected as f32);
+    }
+    let q = joints.map(|v| v as f64);
+    assert_pose_near(&chain.tip(&Pose::IDENTITY, &q), &Pose::from_tip(&target));
+}
@@ -0,0 +1,76 @@
+use openloong_sdk_rust::estimation::{ImuConfig, ImuEstimator, LowPass, OrientationFilter};
+use openloong_sdk_rust::sdk::sens::SensData;
+
+fn sample(t: f64, pitch: f32) -> SensData {
+    let mut sens = SensData::new(19, 6, 6);
+    sens.timestamp = t;
+    sens.rpy = [0.0, pitch, 0.0];
+    sens.acc = [-9.81 * pitch.sin(), 0.0, 9.81 * pitch.cos()];
+    sens
+}
+
+#[test]
+fn test_low_pass_converges() {
+    let mut lp = LowPass::new(5.0);
+    lp.step([0.0; 3], 0.01);
+    let mut y = [0.0; 3];
+    for _ in 0..200 {
+        y = lp.step([1.0, 2.0, 3.0], 0.01);
+    }
+    assert!((y[2] - 3.0).abs() < 1e-3);
+}
+
+#[test]
+fn test_madgwick_tracks_static_tilt() {
+    let mut imu = ImuEstimator::new(ImuConfig {
+        orientation: OrientationFilter::Madgwick,
+        madgwick_beta: 0.5,
+        ..Default::default()
+    });
+    // start upright, then hold a 0.3 rad pitch
+    imu.step(&sample(0.0, 0.0));
+    for i in 1..2000 {
+        imu.step(&sample(i as f64 * 0.005, 0.3));
+    }
+    let state = imu.state();
+    assert!((state.rpy[1] - 0.3).abs() < 0.01, "{:?}", state.rpy);
+    assert!((state.tilt - 0.3).abs() < 0.01);
+    assert!(!state.falling);
+}
+
+#[test]
+fn test_fall_and_abnormal_acc_detection() {
+    let mut imu = ImuEstimator::default();
+    let mut sens = sample(0.0, 1.2);
+    assert!(imu.step(&sens).falling);
+
+    sens.timestamp = 0.01;
+    sens.acc = [0.0, 0.0, 0.5];
+    let mut imu = ImuEstimator::new(ImuConfig {
+        acc_cutoff_hz: 0.0,
+        ..Default::default()
+    });
+    assert!(imu.step(&sens).abnormal_acc);
+}
+
+#[test]
+fn test_repeated_timestamp_is_skipped() {
+    let mut imu = ImuEstimator::new(ImuConfig {
+        orientation: OrientationFilter::Complementary,
+        ..Default::default()
+    });
+    imu.step(&sample(0.0, 0.0));
+    for i in 1..50 {
+        imu.step(&sample(i as f64 * 0.01, 0.3));
+    }
+    let before = imu.state().rpy;
+    // a duplicate would otherwise restart the filters from this raw rpy
+    let mut sens = sample(0.49, 0.3);
+    sens.rpy = [0.0, 1.0, 0.0];
+    assert_eq!(imu.step(&sens).rpy, before);
+
+    // going back in time restarts and takes the sample
+    sens.timestamp = 0.2;
+    assert_eq!(imu.step(&sens).timestamp, 0.2);
+    assert_ne!(imu.state().rpy, before);
+}
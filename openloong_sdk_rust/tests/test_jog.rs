@@ -0,0 +1,137 @@
+use std::f32::consts::FRAC_PI_2;
+use std::io::{Error, ErrorKind};
+use std::sync::{Arc, Mutex};
+
+use openloong_sdk_rust::app::preset_movement::JogFrame;
+use openloong_sdk_rust::param::LoongManiParam;
+use openloong_sdk_rust::sdk::LoongManiSdk;
+use openloong_sdk_rust::sdk::arm::{Axis, Side, TipPose};
+use openloong_sdk_rust::sdk::transport::Transport;
+
+#[derive(Clone, Default)]
+struct CountingLink(Arc<Mutex<usize>>);
+
+impl Transport for CountingLink {
+    fn send(&self, buf: &[u8]) -> Result<usize, Error> {
+        *self.0.lock().unwrap() += 1;
+        Ok(buf.len())
+    }
+
+    fn recv(&self, _buf: &mut [u8]) -> Result<usize, Error> {
+        Err(ErrorKind::WouldBlock.into())
+    }
+}
+
+fn sdk(extra: &str) -> (LoongManiSdk, CountingLink) {
+    let param = LoongManiParam::from_toml_str(
+        &format!(
+            "jnt_num = 19\narm_dof = 7\nfinger_dof_left = 6\nfinger_dof_right = 6\n\
+             neck_dof = 2\nlumbar_dof = 3\ntarget_addr = \"127.0.0.1:8003\"\n{}",
+            extra
+        ),
+        None,
+    )
+    .unwrap();
+    let link = CountingLink::default();
+    let mut sdk = LoongManiSdk::builder()
+        .param(param)
+        .transport(link.clone())
+        .build()
+        .unwrap();
+    // tip yawed by 90 degrees: tool x points along body y
+    sdk.ctrl_mut().arm_mut(Side::Left).set_pose(TipPose::new(
+        [0.3, 0.2, 0.0],
+        [0.0, 0.0, FRAC_PI_2],
+        0.5,
+    ));
+    (sdk, link)
+}
+
+fn close(a: [f32; 3], b: [f32; 3]) -> bool {
+    a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-5)
+}
+
+#[test]
+fn test_jog_body_and_tool_frame() {
+    let (mut sdk, link) = sdk("");
+    sdk.jog(Side::Left, Axis::X, 0.1);
+    assert!(close(
+        sdk.ctrl().arm(Side::Left).pose().xyz,
+        [0.4, 0.2, 0.0]
+    ));
+
+    sdk.jog_in(Side::Left, Axis::X, 0.1, JogFrame::Tool);
+    assert!(close(
+        sdk.ctrl().arm(Side::Left).pose().xyz,
+        [0.4, 0.3, 0.0]
+    ));
+
+    // about the tool x axis, which points along body y
+    sdk.jog_in(Side::Left, Axis::Roll, 0.2, JogFrame::Tool);
+    let pose = *sdk.ctrl().arm(Side::Left).pose();
+    assert!(close(pose.rpy, [0.2, 0.0, FRAC_PI_2]));
+    assert!(close(pose.xyz, [0.4, 0.3, 0.0]));
+
+    // about body x, which is tool -y
+    sdk.jog_in(Side::Left, Axis::Roll, -0.2, JogFrame::Tool);
+    sdk.jog_in(Side::Left, Axis::Roll, 0.2, JogFrame::Body);
+    let pose = *sdk.ctrl().arm(Side::Left).pose();
+    assert!(close(pose.rpy, [0.0, -0.2, FRAC_PI_2]));
+
+    sdk.jog(Side::Left, Axis::Elbow, -0.1);
+    assert!((sdk.ctrl().arm(Side::Left).pose().elbow_angle - 0.4).abs() < 1e-6);
+    assert_eq!(*link.0.lock().unwrap(), 0);
+}
+
+#[test]
+fn test_jog_step_and_frame_from_param() {
+    let (mut sdk, _) = sdk("[preset]\nframe = \"tool\"\n[preset.step]\nx = 0.02\n");
+    sdk.jog_step(Side::Left, Axis::X, -1.0);
+    assert!(close(
+        sdk.ctrl().arm(Side::Left).pose().xyz,
+        [0.3, 0.18, 0.0]
+    ));
+    // right arm untouched
+    assert_eq!(sdk.ctrl().arm(Side::Right).pose().xyz, [0.2, -0.3, 0.1]);
+}
+
+#[test]
+fn test_set_tip_and_finger_do_not_send() {
+    let (mut sdk, link) = sdk("");
+    sdk.set_tip(Side::Right, [0.1, -0.2, 0.3, 0.0, 0.1, 0.0]);
+    let pose = sdk.ctrl().arm(Side::Right).pose();
+    assert_eq!(pose.xyz, [0.1, -0.2, 0.3]);
+    assert_eq!(pose.elbow_angle, 0.5);
+
+    assert!(sdk.set_finger_cmd(Side::Left, &[10.0; 5]).is_err());
+    sdk.set_finger_cmd(Side::Left, &[10.0; 6]).unwrap();
+    assert_eq!(sdk.ctrl().finger(Side::Left).to_vec(), vec![10.0; 6]);
+    assert_eq!(*link.0.lock().unwrap(), 0);
+}
+
+#[test]
+fn test_jog_keeps_rpy_continuous() {
+    use std::f32::consts::PI;
+    let (mut sdk, _) = sdk("");
+    let start = TipPose::new([0.3, 0.2, 0.0], [PI, 0.3, 0.5], 0.5);
+    sdk.ctrl_mut().arm_mut(Side::Left).set_pose(start);
+
+    // linear jogs leave the commanded angles untouched
+    sdk.jog(Side::Left, Axis::Z, 0.1);
+    sdk.jog_in(Side::Left, Axis::Y, 0.1, JogFrame::Tool);
+    let pose = *sdk.ctrl().arm(Side::Left).pose();
+    assert_eq!(pose.rpy, start.rpy);
+    assert_eq!(pose.elbow_angle, start.elbow_angle);
+
+    // a rotation and back lands on roll = pi again, not -pi
+    sdk.jog(Side::Left, Axis::Roll, 0.1);
+    sdk.jog(Side::Left, Axis::Roll, -0.1);
+    assert!(close(sdk.ctrl().arm(Side::Left).pose().rpy, start.rpy));
+
+    // near the pitch singularity yaw is not folded into roll
+    let start = TipPose::new([0.3, 0.2, 0.0], [0.2, FRAC_PI_2 - 1e-4, 0.4], 0.5);
+    sdk.ctrl_mut().arm_mut(Side::Left).set_pose(start);
+    sdk.jog(Side::Left, Axis::Yaw, 0.0);
+    let rpy = sdk.ctrl().arm(Side::Left).pose().rpy;
+    assert!((rpy[2] - 0.4).abs() < 1e-3, "{rpy:?}");
+}
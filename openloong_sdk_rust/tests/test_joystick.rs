@@ -0,0 +1,94 @@
+use openloong_sdk_rust::app::joystick::{JoystickConfig, JoystickTeleop, StickMode};
+use openloong_sdk_rust::param::LoongManiParam;
+use openloong_sdk_rust::sdk::LoongManiSdk;
+use openloong_sdk_rust::sdk::arm::{Axis, Side};
+
+const PARAM: &str = "jnt_num = 19\narm_dof = 7\nfinger_dof_left = 6\nfinger_dof_right = 6\n\
+                     neck_dof = 2\nlumbar_dof = 3\ntarget_addr = \"127.0.0.1:8003\"\n";
+
+#[test]
+fn test_shape_deadzone_and_expo() {
+    let config = JoystickConfig {
+        deadzone: 0.2,
+        expo: 0.5,
+        ..Default::default()
+    };
+    assert_eq!(config.shape(0.15), 0.0);
+    assert_eq!(config.shape(-0.2), 0.0);
+    assert!((config.shape(1.0) - 1.0).abs() < 1e-6);
+    assert!((config.shape(-1.5) + 1.0).abs() < 1e-6);
+    // halfway through the live range: 0.5 * 0.5 + 0.5 * 0.125
+    assert!((config.shape(0.6) - 0.3125).abs() < 1e-6);
+}
+
+#[test]
+fn test_mapping_from_toml() {
+    let param = LoongManiParam::from_toml_str(
+        &format!(
+            "{}[joystick]\ndeadzone = 0.0\nexpo = 0.0\nmax_linear = 0.2\n\
+             translate = [{{ joy = 2, axis = \"z\", scale = -1.0 }}]\n",
+            PARAM
+        ),
+        None,
+    )
+    .unwrap();
+    let teleop = JoystickTeleop::new(param.joystick().clone());
+    let vel = teleop.velocity(&[1.0, 1.0, 0.5, 0.0]);
+    assert_eq!(vel, [0.0, 0.0, -0.1, 0.0, 0.0, 0.0, 0.0]);
+
+    let bad = format!(
+        "{}[joystick]\ntranslate = [{{ joy = 4, axis = \"x\" }}]\n",
+        PARAM
+    );
+    assert!(LoongManiParam::from_toml_str(&bad, None).is_err());
+}
+
+#[test]
+fn test_buttons_switch_on_press() {
+    let param = LoongManiParam::from_toml_str(
+        &format!("{}[joystick]\ndeadzone = 0.0\nexpo = 0.0\n", PARAM),
+        None,
+    )
+    .unwrap();
+    let mut sdk = LoongManiSdk::from_param(&param).unwrap();
+    let mut teleop = JoystickTeleop::new(param.joystick().clone());
+    let start = *sdk.ctrl().arm(Side::Right).pose();
+
+    // held at startup: ignored
+    sdk.sens_mut().key = [0b001, 0];
+    sdk.joystick(&mut teleop, 0.02).unwrap();
+    assert_eq!(teleop.side(), Side::Left);
+
+    sdk.sens_mut().key = [0, 0];
+    sdk.joystick(&mut teleop, 0.02).unwrap();
+    sdk.sens_mut().key = [0b101, 0];
+    sdk.joystick(&mut teleop, 0.02).unwrap();
+    assert_eq!(teleop.side(), Side::Right);
+    assert_eq!(teleop.mode(), StickMode::Rotate);
+
+    // still held: no second toggle; stick 3 drives the elbow in rotate mode
+    sdk.sens_mut().joy = [0.0, 0.0, 0.0, 1.0];
+    sdk.joystick(&mut teleop, 0.5).unwrap();
+    assert_eq!(teleop.side(), Side::Right);
+    let pose = sdk.ctrl().arm(Side::Right).pose();
+    assert!((pose.get(Axis::Elbow) - start.elbow_angle - 0.25).abs() < 1e-6);
+    assert_eq!(pose.xyz, start.xyz);
+}
+
+#[test]
+fn test_reload_reaches_joystick() {
+    let param = LoongManiParam::from_toml_str(PARAM, None).unwrap();
+    let mut sdk = LoongManiSdk::from_param(&param).unwrap();
+    let mut teleop = JoystickTeleop::new(param.joystick().clone());
+    let reloaded = format!("{}[joystick]\nmax_linear = 0.2\n", PARAM);
+    sdk.reload_param(LoongManiParam::from_toml_str(&reloaded, None).unwrap())
+        .unwrap();
+
+    // stick 1 drives x in translate mode, full deflection for 0.5 s
+    let x = sdk.ctrl().arm(Side::Left).pose().xyz[0];
+    sdk.sens_mut().joy = [0.0, 1.0, 0.0, 0.0];
+    sdk.joystick(&mut teleop, 0.5).unwrap();
+    assert_eq!(teleop.config().max_linear, 0.2);
+    let dx = sdk.ctrl().arm(Side::Left).pose().xyz[0] - x;
+    assert!((dx - 0.1).abs() < 1e-6);
+}
@@ -0,0 +1,122 @@
+use std::f64::consts::FRAC_PI_2;
+
+use openloong_sdk_rust::geometry::Pose;
+use openloong_sdk_rust::kinematics::{Chain, Dh, Joint, KinematicsConfig};
+use openloong_sdk_rust::param::{LoongManiParam, ParamError};
+use openloong_sdk_rust::sdk::arm::Side;
+use openloong_sdk_rust::sdk::sens::SensData;
+
+fn assert_pose_near(a: &Pose, b: &Pose) {
+    for i in 0..3 {
+        assert!((a.p[i] - b.p[i]).abs() < 1e-9, "{:?} != {:?}", a, b);
+    }
+    assert!(a.q.angle_to(&b.q) < 1e-6, "{:?} != {:?}", a, b);
+}
+
+#[test]
+fn test_dh_matches_axis_form() {
+    let dh = Chain {
+        joints: vec![Joint::from_dh(
+            "j",
+            Dh {
+                a: 0.2,
+                alpha: FRAC_PI_2,
+                d: 0.1,
+                theta: 0.0,
+            },
+        )],
+        ..Default::default()
+    };
+    let axis = Chain {
+        joints: vec![Joint::new("j", [0.0; 6], [0.0, 0.0, 1.0])],
+        tool: [0.2, 0.0, 0.1, FRAC_PI_2, 0.0, 0.0],
+        ..Default::default()
+    };
+    for q in [0.0, 0.3, -1.2] {
+        assert_pose_near(
+            &dh.tip(&Pose::IDENTITY, &[q]),
+            &axis.tip(&Pose::IDENTITY, &[q]),
+        );
+    }
+}
+
+#[test]
+fn test_default_arms() {
+    let config = KinematicsConfig::default();
+    let links = config.forward(&[0.0; 19]).unwrap();
+    assert_eq!(links.arm(Side::Left).len(), 8);
+    let tip = links.tip(Side::Left);
+    assert_pose_near(
+        &tip,
+        &Pose::from_xyz_rpy([0.0, 0.2, -0.58], [0.0, FRAC_PI_2, 0.0]),
+    );
+    assert_pose_near(&links.torso(), &Pose::IDENTITY);
+    assert!((links.head().p[2] - 0.55).abs() < 1e-9);
+
+    // equal joint angles give mirrored arms
+    let arm = [0.3, 0.4, -0.2, -1.0, 0.5, 0.2, -0.3];
+    let mut q = [0.0; 19];
+    q[..7].copy_from_slice(&arm);
+    q[7..14].copy_from_slice(&arm);
+    let links = config.forward(&q).unwrap();
+    let left = links.tip(Side::Left).to_tip(0.0).mirrored();
+    let right = links.tip(Side::Right).to_tip(0.0);
+    for i in 0..6 {
+        assert!((left.to_array()[i] - right.to_array()[i]).abs() < 1e-5);
+    }
+
+    // leaning the torso carries both arms with it
+    q[17] = 0.3;
+    let leaned = config.forward(&q).unwrap();
+    let expected = leaned.torso().compose(&links.tip(Side::Left));
+    assert_pose_near(&leaned.tip(Side::Left), &expected);
+    assert!(config.forward(&[0.0; 10]).is_err());
+}
+
+#[test]
+fn test_calibration_drift() {
+    let config = KinematicsConfig::default();
+    let mut sens = SensData::new(19, 6, 6);
+    sens.act_j[3] = -1.2;
+    sens.act_j[10] = -0.6;
+    let links = config.forward(&sens.act_j.to_vec()).unwrap();
+    for side in Side::BOTH {
+        let tip = links.tip(side).to_tip(0.0);
+        sens.act_tip_p_rpy2b[side.index()] = [
+            tip.xyz[0], tip.xyz[1], tip.xyz[2], tip.rpy[0], tip.rpy[1], tip.rpy[2],
+        ];
+    }
+    sens.act_tip_p_rpy2b[1][2] += 0.05;
+
+    let drift = config.drift(&sens).unwrap();
+    assert!(drift[0].position < 1e-5 && drift[0].angle < 1e-5);
+    assert!(!drift[0].exceeds(&config));
+    assert!((drift[1].position - 0.05).abs() < 1e-5);
+    assert!(drift[1].exceeds(&config));
+}
+
+#[test]
+fn test_kinematics_param() {
+    let base = "jnt_num = 19\narm_dof = 7\nfinger_dof_left = 6\nfinger_dof_right = 6\n\
+                neck_dof = 2\nlumbar_dof = 3\ntarget_addr = \"127.0.0.1:8003\"\n";
+    let text = format!(
+        "{}[kinematics]\ndrift_angle = 0.0\n[kinematics.neck]\nbase = [0.0, 0.0, 0.5, 0.0, 0.0, 0.0]\n\
+         joints = [{{ name = \"yaw\", dh = {{ alpha = -1.5708 }}, min = 1.0, max = -1.0 }}]\n",
+        base
+    );
+    let err = LoongManiParam::from_toml_str(&text, None).unwrap_err();
+    let err = err.downcast_ref::<ParamError>().unwrap();
+    let keys: Vec<&str> = err.issues().iter().map(|i| i.key.as_str()).collect();
+    assert_eq!(
+        keys,
+        [
+            "kinematics.neck.joints",
+            "kinematics.neck.joints[0]",
+            "kinematics.drift_angle"
+        ]
+    );
+
+    let param = LoongManiParam::from_toml_str(base, None).unwrap();
+    assert_eq!(param.kinematics(), &KinematicsConfig::default());
+    assert_eq!(param.kinematics().left_arm.joints[3].name, "elbow");
+}
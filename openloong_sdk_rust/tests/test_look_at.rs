@@ -0,0 +1,38 @@
+use openloong_sdk_rust::app::look_at::{GazeTracker, NeckKinematics};
+
+#[test]
+fn test_look_at_angles() {
+    let kin = NeckKinematics {
+        pivot: [0.0; 3],
+        eye_height: 0.0,
+        ..Default::default()
+    };
+    let q = kin.look_at([1.0, 1.0, 0.0]);
+    assert!((q[0] - std::f64::consts::FRAC_PI_4).abs() < 1e-9);
+    assert!(q[1].abs() < 1e-9);
+    // below and ahead: pitch down by 45 degrees
+    let q = kin.look_at([0.5, 0.0, -0.5]);
+    assert!((q[1] - std::f64::consts::FRAC_PI_4).abs() < 1e-9);
+    // behind the robot: yaw is clamped
+    let q = kin.look_at([-1.0, 0.1, 0.0]);
+    assert_eq!(q[0], kin.yaw_limit[1]);
+}
+
+#[test]
+fn test_gaze_tracker_rate_limit() {
+    let kin = NeckKinematics {
+        pivot: [0.0; 3],
+        eye_height: 0.0,
+        max_vel: [1.0, 1.0],
+        ..Default::default()
+    };
+    let mut tracker = GazeTracker::new(kin);
+    tracker.reset([0.0, 0.0]);
+    let q = tracker.step([0.0, 1.0, 0.0], 0.1);
+    assert!((q[0] - 0.1).abs() < 1e-9);
+    for _ in 0..100 {
+        tracker.step([0.0, 1.0, 0.0], 0.1);
+    }
+    let q = tracker.current().unwrap();
+    assert!((q[0] - 1.2).abs() < 1e-9);
+}
@@ -0,0 +1,110 @@
+use openloong_sdk_rust::app::pose_library::{NamedPose, PoseLibrary};
+use openloong_sdk_rust::param::LoongManiParam;
+use openloong_sdk_rust::sdk::LoongManiSdk;
+use openloong_sdk_rust::sdk::arm::{Side, TipPose};
+
+fn sdk() -> LoongManiSdk {
+    let param = LoongManiParam::from_toml_str(
+        "jnt_num = 19\narm_dof = 7\nfinger_dof_left = 6\nfinger_dof_right = 1\n\
+         neck_dof = 2\nlumbar_dof = 3\ntarget_addr = \"127.0.0.1:8003\"\n",
+        None,
+    )
+    .unwrap();
+    LoongManiSdk::from_param(&param).unwrap()
+}
+
+fn home() -> NamedPose {
+    NamedPose {
+        left: [0.4, 0.3, 0.1, 0.1, 0.2, 0.3, 0.5],
+        right: [0.2, -0.3, 0.1, 0.0, 0.0, 0.0, 0.5],
+        finger_left: vec![10.0; 6],
+        finger_right: vec![0.0],
+        neck: vec![0.2, 0.1],
+        lumbar: vec![0.1, 0.2, 0.3],
+    }
+}
+
+#[test]
+fn test_mirror_pose() {
+    let tip = TipPose::new([0.4, 0.3, 0.1], [0.1, 0.2, 0.3], 0.5);
+    let m = tip.mirrored();
+    assert_eq!(m.xyz, [0.4, -0.3, 0.1]);
+    assert_eq!(m.rpy, [-0.1, 0.2, -0.3]);
+    assert_eq!(m.mirrored(), tip);
+
+    let pose = home().mirrored();
+    assert_eq!(pose.right, [0.4, -0.3, 0.1, -0.1, 0.2, -0.3, 0.5]);
+    assert_eq!(pose.left, [0.2, 0.3, 0.1, -0.0, 0.0, -0.0, 0.5]);
+    assert_eq!(pose.finger_right, vec![10.0; 6]);
+    assert_eq!(pose.neck, vec![-0.2, 0.1]);
+    assert_eq!(pose.lumbar, vec![-0.1, 0.2, -0.3]);
+
+    let sym = home().symmetric(Side::Left);
+    assert_eq!(sym.right, [0.4, -0.3, 0.1, -0.1, 0.2, -0.3, 0.5]);
+    assert_eq!(sym.left, home().left);
+}
+
+#[test]
+fn test_library_persists() {
+    let path = std::env::temp_dir().join(format!("loong_poses_{}.toml", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let mut library = PoseLibrary::open(&path).unwrap();
+    assert_eq!(library.names().count(), 0);
+    library.insert("home", home());
+    library.insert("wave", home().mirrored());
+    library.save().unwrap();
+
+    let mut library = PoseLibrary::open(&path).unwrap();
+    assert_eq!(library.names().collect::<Vec<_>>(), ["home", "wave"]);
+    assert_eq!(library.get("home"), Some(&home()));
+    assert!(library.remove("wave").is_some());
+    library.save().unwrap();
+    assert_eq!(PoseLibrary::open(&path).unwrap().names().count(), 1);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_capture_and_go_to_named() {
+    let mut sdk = sdk();
+    sdk.sens_mut().act_tip_p_rpy2b = [[0.3, 0.2, 0.0, 0.0, 0.0, 0.0]; 2];
+    sdk.sens_mut().act_j[14] = 0.4;
+    let captured = sdk.capture_pose().unwrap();
+    assert_eq!(captured.left, [0.3, 0.2, 0.0, 0.0, 0.0, 0.0, 0.5]);
+    assert_eq!(captured.neck, vec![0.4, 0.0]);
+    assert_eq!(captured.finger_right.len(), 1);
+
+    let mut library = PoseLibrary::new();
+    library.insert("home", home());
+    assert!(sdk.go_to_named(&library, "nowhere", 1.0).is_err());
+
+    let start = *sdk.ctrl().arm(Side::Left).pose();
+    let mut motion = sdk.go_to_named(&library, "home", 1.0).unwrap();
+    sdk.play(&mut motion, 0.01).unwrap();
+    // barely moved at the start of the smoothstep
+    let x = sdk.ctrl().arm(Side::Left).pose().xyz[0];
+    assert!((x - start.xyz[0]).abs() < 1e-3);
+    sdk.play(&mut motion, 1.0).unwrap();
+    assert!(motion.is_done());
+    let pose = sdk.ctrl().arm(Side::Left).pose();
+    assert!((pose.rpy[2] - 0.3).abs() < 1e-5);
+    assert_eq!(sdk.ctrl().neck_cmd().to_vec(), vec![0.2, 0.1]);
+    assert_eq!(sdk.ctrl().finger(Side::Left).to_vec(), vec![10.0; 6]);
+}
+
+#[test]
+fn test_motion_keeps_rpy_continuous() {
+    use openloong_sdk_rust::app::pose_library::PoseMotion;
+    use std::f32::consts::PI;
+    let start = home();
+    let mut target = home();
+    target.left = [0.4, 0.3, 0.2, PI, 0.2, 0.3, 0.5];
+    let mut motion = PoseMotion::new(start.clone(), target.clone(), 1.0);
+    let mut previous = start.left[3];
+    while !motion.is_done() {
+        let roll = motion.step(0.1).left[3];
+        // heads toward +pi and never jumps to the -pi branch
+        assert!(roll >= previous - 1e-6 && roll <= PI + 1e-5, "{roll}");
+        previous = roll;
+    }
+    assert_eq!(motion.step(0.1), target);
+}
@@ -0,0 +1,64 @@
+use openloong_sdk_rust::app::posture::{LumbarConfig, PostureController, TorsoPosture};
+use openloong_sdk_rust::geometry::Pose;
+use openloong_sdk_rust::param::LoongManiParam;
+use openloong_sdk_rust::sdk::LoongManiSdk;
+use openloong_sdk_rust::sdk::arm::{Side, TipPose};
+use openloong_sdk_rust::sdk::ctrl::ArmMode;
+
+#[test]
+fn test_lean_only_outside_reach() {
+    let ctl = PostureController::new(LumbarConfig::default());
+    assert_eq!(
+        ctl.lean_toward(Side::Left, [0.4, 0.2, 0.1]),
+        TorsoPosture::default()
+    );
+    let lean = ctl.lean_toward(Side::Left, [0.9, 0.2, 0.1]);
+    assert!(lean.pitch > 0.0);
+    assert!(lean.pitch <= ctl.config().max[1]);
+}
+
+#[test]
+fn test_posture_rate_limit_and_compensation() {
+    let mut ctl = PostureController::new(LumbarConfig::default());
+    let goal = TorsoPosture {
+        pitch: 0.3,
+        ..Default::default()
+    };
+    let p = ctl.step(goal, 0.1);
+    assert!((p.pitch - 0.03).abs() < 1e-9);
+    ctl.reset(goal);
+
+    // the pivot stays put, and a compensated target maps back to itself
+    let pivot = ctl.config().pivot;
+    let torso = ctl.torso_pose();
+    let moved = torso.transform_point(pivot);
+    for i in 0..3 {
+        assert!((moved[i] - pivot[i]).abs() < 1e-9);
+    }
+    let target = Pose::from_xyz_rpy([0.7, 0.2, 0.0], [0.0; 3]);
+    let back = torso.compose(&ctl.to_torso(&target));
+    for i in 0..3 {
+        assert!((back.p[i] - target.p[i]).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_reach_keeps_roll_pi() {
+    let param = LoongManiParam::from_toml_str(
+        "jnt_num = 19\narm_dof = 7\nfinger_dof_left = 6\nfinger_dof_right = 6\n\
+         neck_dof = 2\nlumbar_dof = 3\ntarget_addr = \"127.0.0.1:8003\"\n",
+        None,
+    )
+    .unwrap();
+    let mut sdk = LoongManiSdk::from_param(&param).unwrap();
+    let tip = TipPose::new([0.4, 0.2, 0.1], [std::f32::consts::PI, 0.2, 0.3], 0.5);
+    sdk.ctrl_mut()
+        .set_arm_mode(ArmMode::CartesianBodyFrame)
+        .arm_mut(Side::Left)
+        .set_pose(tip);
+    let mut ctl = PostureController::new(param.lumbar().clone());
+    // inside the envelope, no lean: the command stays as it was
+    sdk.reach(&mut ctl, Side::Left, &Pose::from_tip(&tip), 0.5, 0.02)
+        .unwrap();
+    assert_eq!(*sdk.ctrl().arm(Side::Left).pose(), tip);
+}
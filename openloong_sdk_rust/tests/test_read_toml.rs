@@ -1,13 +1,144 @@
 use openloong_sdk_rust::param::LoongManiParam;
 
+const PARAM_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/param/param.toml");
+
 #[test]
 fn test_read_toml() {
-    let param = LoongManiParam::read_from_toml().unwrap();
+    let param = LoongManiParam::load(PARAM_PATH).unwrap();
     assert_eq!(param.jnt_num(), 19);
     assert_eq!(param.arm_dof(), 7);
-    assert_eq!(param.finger_dof_left(), 1);
-    assert_eq!(param.finger_dof_right(), 1);
+    assert_eq!(param.finger_dof_left(), 6);
+    assert_eq!(param.finger_dof_right(), 6);
     assert_eq!(param.neck_dof(), 2);
     assert_eq!(param.lumbar_dof(), 3);
+    assert_eq!(param.target_addr(), "0.0.0.0:8003");
+    assert_eq!(param.profile(), None);
+}
+
+#[test]
+fn test_read_toml_profile() {
+    let param = LoongManiParam::load_profile(PARAM_PATH, Some("real")).unwrap();
     assert_eq!(param.target_addr(), "192.168.1.201:8003");
+    assert_eq!(param.profile(), Some("real"));
+    assert!(LoongManiParam::load_profile(PARAM_PATH, Some("moon")).is_err());
+}
+
+#[test]
+fn test_profile_merges_nested_tables() {
+    let param = LoongManiParam::from_toml_str(
+        r#"
+        jnt_num = 19
+        arm_dof = 7
+        finger_dof_left = 6
+        finger_dof_right = 6
+        neck_dof = 2
+        lumbar_dof = 3
+        target_addr = "0.0.0.0:8003"
+
+        [init]
+        arm_mode = "none"
+        neck = [0.1, 0.2]
+
+        [profile.real.init]
+        neck = [0.3, 0.4]
+        "#,
+        Some("real"),
+    )
+    .unwrap();
+    assert_eq!(param.init().neck, vec![0.3, 0.4]);
+    assert_eq!(
+        param.init().arm_mode,
+        openloong_sdk_rust::sdk::ctrl::ArmMode::None
+    );
+}
+
+#[test]
+fn test_ctrl_init_from_toml() {
+    use openloong_sdk_rust::sdk::LoongManiSdk;
+    use openloong_sdk_rust::sdk::arm::Side;
+    use openloong_sdk_rust::sdk::ctrl::{ArmMode, CtrlData, InCharge};
+
+    let param: LoongManiParam = toml::from_str(
+        r#"
+        jnt_num = 19
+        arm_dof = 7
+        finger_dof_left = 6
+        finger_dof_right = 1
+        neck_dof = 2
+        lumbar_dof = 3
+        target_addr = "0.0.0.0:8003"
+
+        [init]
+        in_charge = "mani_ctrl_disable"
+        arm_mode = "none"
+        arm_left = [0.3, 0.2, 0.0, 0.0, 0.0, 0.0, 0.4]
+        "#,
+    )
+    .unwrap();
+    let ctrl = CtrlData::from_param(&param).unwrap();
+    assert_eq!(ctrl.in_charge(), InCharge::ManiCtrlDisable);
+    assert_eq!(ctrl.arm_mode(), ArmMode::None);
+    assert_eq!(ctrl.arm(Side::Left).pose().elbow_angle, 0.4);
+    assert_eq!(ctrl.arm(Side::Right).pose().xyz, [0.2, -0.3, 0.1]);
+
+    let bad: LoongManiParam = toml::from_str(
+        r#"
+        jnt_num = 19
+        arm_dof = 6
+        finger_dof_left = 6
+        finger_dof_right = 6
+        neck_dof = 2
+        lumbar_dof = 3
+        target_addr = "0.0.0.0:8003"
+        [init]
+        neck = [0.0]
+        "#,
+    )
+    .unwrap();
+    assert!(CtrlData::from_param(&bad).is_err());
+    assert!(LoongManiSdk::from_param(&bad).is_err());
+    assert!(CtrlData::new(6, 6, 1, 2, 3).is_err());
+}
+
+#[test]
+fn test_validate_reports_every_problem() {
+    let err = LoongManiParam::from_toml_str(
+        r#"
+        jnt_num = 20
+        arm_dof = 7
+        finger_dof_left = 6
+        finger_dof_right = 2
+        neck_dof = 2
+        lumbar_dof = -3
+        target_addr = "robot:8003"
+
+        [init]
+        lumbar = [0.0, 0.0, 0.0]
+
+        [neck]
+        yaw_limit = [1.0, -1.0]
+
+        [workspace]
+        min = [0.0, 0.5, 0.0]
+        max = [0.6, -0.5, 0.5]
+        "#,
+        None,
+    )
+    .unwrap_err();
+    let err = err
+        .downcast_ref::<openloong_sdk_rust::param::ParamError>()
+        .unwrap();
+    let keys: Vec<&str> = err.issues().iter().map(|i| i.key.as_str()).collect();
+    assert_eq!(
+        keys,
+        [
+            "lumbar_dof",
+            "jnt_num",
+            "finger_dof_right",
+            "target_addr",
+            "init.lumbar",
+            "neck.yaw_limit",
+            "workspace.min[1]"
+        ]
+    );
 }
@@ -0,0 +1,188 @@
+use std::io::Error;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use openloong_sdk_rust::app::look_at::GazeTracker;
+use openloong_sdk_rust::app::posture::{PostureController, TorsoPosture};
+use openloong_sdk_rust::param::{LoongManiParam, ParamWatcher};
+use openloong_sdk_rust::sdk::LoongManiSdk;
+use openloong_sdk_rust::sdk::arm::{Axis, Side};
+use openloong_sdk_rust::sdk::collision::{CollisionChecker, CollisionConfig};
+use openloong_sdk_rust::sdk::ctrl::{ArmMode, CtrlData, FiltLevel};
+use openloong_sdk_rust::sdk::logger::PacketLogger;
+use openloong_sdk_rust::sdk::safety::WorkspaceLimiter;
+use openloong_sdk_rust::sdk::transport::Transport;
+
+const PARAM: &str = r#"
+jnt_num = 19
+arm_dof = 7
+finger_dof_left = 6
+finger_dof_right = 6
+neck_dof = 2
+lumbar_dof = 3
+target_addr = "127.0.0.1:8003"
+"#;
+
+fn param(extra: &str) -> LoongManiParam {
+    LoongManiParam::from_toml_str(&format!("{}{}", PARAM, extra), None).unwrap()
+}
+
+struct Sink;
+
+impl Transport for Sink {
+    fn send(&self, buf: &[u8]) -> Result<usize, Error> {
+        Ok(buf.len())
+    }
+
+    fn recv(&self, _buf: &mut [u8]) -> Result<usize, Error> {
+        Err(std::io::ErrorKind::WouldBlock.into())
+    }
+}
+
+/// Left tip targets as they are sent.
+#[derive(Clone, Default)]
+struct TipLog(Arc<Mutex<Vec<[f32; 3]>>>);
+
+impl PacketLogger for TipLog {
+    fn sent(&self, ctrl: &CtrlData) {
+        self.0.lock().unwrap().push(ctrl.arm(Side::Left).pose().xyz);
+    }
+}
+
+/// Send a target outside every workspace and return what went out.
+fn sent_tip(sdk: &mut LoongManiSdk, log: &TipLog) -> [f32; 3] {
+    sdk.ctrl_mut()
+        .set_arm_mode(ArmMode::CartesianBodyFrame)
+        .arm_mut(Side::Left)
+        .pose_mut()
+        .xyz = [0.9, 0.1, -1.0];
+    sdk.send().unwrap();
+    *log.0.lock().unwrap().last().unwrap()
+}
+
+#[test]
+fn test_reload_applies_tuning() {
+    let mut sdk = LoongManiSdk::from_param(&param("")).unwrap();
+    sdk.reload_param(param(
+        "[init]\nfilt_level = \"level3\"\n[imu]\nfall_tilt = 0.5\n[preset.step]\nz = 0.01\n",
+    ))
+    .unwrap();
+    assert_eq!(sdk.ctrl().filt_level(), FiltLevel::Level3);
+    assert_eq!(sdk.param().preset().step.z, 0.01);
+    assert_eq!(sdk.imu().config().fall_tilt, 0.5);
+
+    let z = sdk.ctrl().arm(Side::Left).pose().xyz[2];
+    sdk.jog_step(Side::Left, Axis::Z, 1.0);
+    let dz = sdk.ctrl().arm(Side::Left).pose().xyz[2] - z;
+    assert!((dz - 0.01).abs() < 1e-6);
+}
+
+#[test]
+fn test_reload_rejects_structural_change() {
+    let mut sdk = LoongManiSdk::from_param(&param("")).unwrap();
+    let changed = LoongManiParam::from_toml_str(
+        &format!("{}[preset.step]\nx = 0.01\n", PARAM).replace("127.0.0.1", "127.0.0.2"),
+        None,
+    )
+    .unwrap();
+    assert!(sdk.reload_param(changed).is_err());
+    // nothing applied
+    assert_eq!(sdk.param().preset().step.x, 0.05);
+    assert_eq!(sdk.param().target_addr(), "127.0.0.1:8003");
+
+    // the kinematic model is built once
+    assert!(
+        sdk.reload_param(param("[kinematics]\ndrift_position = 0.05\n"))
+            .is_err()
+    );
+    assert_eq!(sdk.param().kinematics().drift_position, 0.02);
+}
+
+#[test]
+fn test_reload_applies_workspace() {
+    let log = TipLog::default();
+    let mut sdk = LoongManiSdk::builder()
+        .param(param(""))
+        .transport(Sink)
+        .logger(log.clone())
+        .build()
+        .unwrap();
+    assert_eq!(sent_tip(&mut sdk, &log), [0.9, 0.1, -1.0]);
+
+    let workspace = "[workspace]\nmin = [0.0, -0.5, -0.3]\nmax = [0.6, 0.5, 0.5]\n";
+    sdk.reload_param(param(workspace)).unwrap();
+    assert_eq!(sent_tip(&mut sdk, &log), [0.6, 0.1, -0.3]);
+
+    sdk.reload_param(param("")).unwrap();
+    assert_eq!(sent_tip(&mut sdk, &log), [0.9, 0.1, -1.0]);
+}
+
+#[test]
+fn test_reload_keeps_builder_parts() {
+    let log = TipLog::default();
+    let checker = CollisionChecker::new(CollisionConfig {
+        margin: 0.05,
+        ..Default::default()
+    });
+    let mut sdk = LoongManiSdk::builder()
+        .param(param(""))
+        .transport(Sink)
+        .logger(log.clone())
+        .safety_limiter(WorkspaceLimiter {
+            min: [0.0, -0.5, -0.3],
+            max: [0.6, 0.5, 0.5],
+        })
+        .collision(checker)
+        .build()
+        .unwrap();
+    sdk.reload_param(param(
+        "[workspace]\nmin = [-1.0, -1.0, -1.0]\nmax = [1.0, 1.0, 1.0]\n\
+         [collision]\nmargin = 0.01\n",
+    ))
+    .unwrap();
+    assert_eq!(sent_tip(&mut sdk, &log), [0.6, 0.1, -0.3]);
+    assert_eq!(sdk.collision().unwrap().config().margin, 0.05);
+    // the param still records the file
+    assert_eq!(sdk.param().collision().unwrap().margin, 0.01);
+}
+
+#[test]
+fn test_watcher_picks_up_changes() {
+    let path = std::env::temp_dir().join(format!("loong_reload_{}.toml", std::process::id()));
+    std::fs::write(&path, PARAM).unwrap();
+    let mut watcher = ParamWatcher::new(&path, None).with_interval(Duration::ZERO);
+    assert!(watcher.poll().is_none());
+
+    std::thread::sleep(Duration::from_millis(20));
+    std::fs::write(&path, format!("{}[preset.step]\nx = 0.02\n", PARAM)).unwrap();
+    let param = watcher.poll().unwrap().unwrap();
+    assert_eq!(param.preset().step.x, 0.02);
+    assert!(watcher.poll().is_none());
+
+    std::thread::sleep(Duration::from_millis(20));
+    std::fs::write(&path, "arm_dof = -1").unwrap();
+    assert!(watcher.poll().unwrap().is_err());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_reload_reaches_neck_and_lumbar() {
+    let mut sdk = LoongManiSdk::from_param(&param("")).unwrap();
+    sdk.ctrl_mut().set_arm_mode(ArmMode::CartesianBodyFrame);
+    let mut tracker = GazeTracker::new(sdk.param().neck().clone());
+    let mut controller = PostureController::new(sdk.param().lumbar().clone());
+    sdk.reload_param(param(
+        "[neck]\nyaw_limit = [-0.3, 0.3]\n[lumbar]\nmax = [0.2, 0.1, 0.5]\n",
+    ))
+    .unwrap();
+
+    // far to the left and far forward, limited by the reloaded tables
+    sdk.look_at(&mut tracker, [0.0, 5.0, 0.45], 10.0).unwrap();
+    assert!((tracker.current().unwrap()[0] - 0.3).abs() < 1e-9);
+    let lean = TorsoPosture {
+        pitch: 1.0,
+        ..Default::default()
+    };
+    let posture = sdk.set_posture(&mut controller, lean, 10.0).unwrap();
+    assert!((posture.pitch - 0.1).abs() < 1e-9);
+}
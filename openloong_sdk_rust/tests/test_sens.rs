@@ -0,0 +1,49 @@
+use ndarray::Array1;
+use openloong_sdk_rust::sdk::sens::SensData;
+
+fn filled(jnt_num: i16, finger_left: i16, finger_right: i16) -> SensData {
+    let mut sens = SensData::new(jnt_num, finger_left, finger_right);
+    let ramp = |n: usize, start: f32| Array1::from_iter((0..n).map(|i| start + i as f32 * 0.5));
+    let n = jnt_num as usize;
+    sens.timestamp = 12.5;
+    sens.plan_name = "walk".to_string();
+    sens.act_j = ramp(n, 1.0);
+    sens.act_t = ramp(n, -3.0);
+    sens.drv_err = Array1::from_iter((0..n as i16).map(|i| i * 3));
+    sens.tgt_j = ramp(n, 100.0);
+    sens.act_finger_left = ramp(finger_left as usize, 0.1);
+    sens.tgt_finger_right = ramp(finger_right as usize, 0.7);
+    sens.act_tip_p_rpy2b[1] = [0.4, -0.2, 0.1, 3.0, 0.0, -1.0];
+    sens.tgt_tip_fm2b[0][5] = 9.0;
+    sens
+}
+
+#[test]
+fn test_sens_round_trip() {
+    // 19 joints and mixed hands, none of the buffers six long
+    for (jnt_num, left, right) in [(19, 6, 1), (17, 1, 1)] {
+        let sent = filled(jnt_num, left, right);
+        let buf = sent.pack_data().unwrap();
+        assert_eq!(buf.len(), sent.get_fmt_size().iter().sum::<usize>());
+
+        let mut got = SensData::new(jnt_num, left, right);
+        got.unpack_data(&buf).unwrap();
+        assert_eq!(got.timestamp, sent.timestamp);
+        assert_eq!(got.plan_name, "walk");
+        assert_eq!(got.act_j, sent.act_j);
+        assert_eq!(got.act_t, sent.act_t);
+        assert_eq!(got.drv_err, sent.drv_err);
+        assert_eq!(got.tgt_j, sent.tgt_j);
+        assert_eq!(got.act_finger_left, sent.act_finger_left);
+        assert_eq!(got.tgt_finger_right, sent.tgt_finger_right);
+        assert_eq!(got.act_tip_p_rpy2b, sent.act_tip_p_rpy2b);
+        assert_eq!(got.tgt_tip_fm2b, sent.tgt_tip_fm2b);
+    }
+}
+
+#[test]
+fn test_sens_short_packet() {
+    let buf = filled(19, 6, 1).pack_data().unwrap();
+    let mut got = SensData::new(19, 6, 1);
+    assert!(got.unpack_data(&buf[..buf.len() - 1]).is_err());
+}
@@ -0,0 +1,76 @@
+use std::net::UdpSocket;
+use std::time::{Duration, Instant};
+
+use openloong_sdk_rust::param::LoongManiParam;
+use openloong_sdk_rust::sdk::LoongManiSdk;
+use openloong_sdk_rust::sdk::socket::{SocketConfig, init_mani_socket};
+
+fn param(target: &str, socket: &str) -> Result<LoongManiParam, Box<dyn std::error::Error>> {
+    LoongManiParam::from_toml_str(
+        &format!(
+            "jnt_num = 19\narm_dof = 7\nfinger_dof_left = 6\nfinger_dof_right = 6\n\
+             neck_dof = 2\nlumbar_dof = 3\ntarget_addr = \"{}\"\n[socket]\n{}",
+            target, socket
+        ),
+        None,
+    )
+}
+
+#[test]
+fn test_bind_with_socket_options() {
+    let robot = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let target = robot.local_addr().unwrap().to_string();
+    let param = param(
+        &target,
+        "bind = \"127.0.0.1:0\"\nrecv_buffer = 65536\nsend_buffer = 65536\n\
+         dscp = 46\nread_timeout = 0.05\n",
+    )
+    .unwrap();
+    let mut sdk = LoongManiSdk::from_param(&param).unwrap();
+    assert!(sdk.local_addr().unwrap().ip().is_loopback());
+
+    sdk.send().unwrap();
+    let mut buf = [0; 2048];
+    let (_, from) = robot.recv_from(&mut buf).unwrap();
+    assert_eq!(from, sdk.local_addr().unwrap());
+
+    // blocking mode waits for the timeout instead of returning at once
+    let start = Instant::now();
+    sdk.recv().unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(40));
+}
+
+#[test]
+fn test_ipv6_target_binds_ipv6() {
+    let Ok(robot) = UdpSocket::bind("[::1]:0") else {
+        return; // no IPv6 loopback here
+    };
+    let target = robot.local_addr().unwrap();
+    let socket = init_mani_socket(&SocketConfig::default(), target).unwrap();
+    assert!(socket.local_addr().unwrap().is_ipv6());
+    socket.send_to(b"ping", target).unwrap();
+    let mut buf = [0; 8];
+    assert_eq!(robot.recv(&mut buf).unwrap(), 4);
+}
+
+#[test]
+fn test_socket_validation() {
+    assert!(param("[::1]:8003", "").is_ok());
+    let err = param(
+        "[::1]:8003",
+        "bind = \"0.0.0.0:8004\"\ndscp = 64\nread_timeout = 0.0\n",
+    )
+    .unwrap_err();
+    let err = err
+        .downcast_ref::<openloong_sdk_rust::param::ParamError>()
+        .unwrap();
+    let keys: Vec<&str> = err.issues().iter().map(|i| i.key.as_str()).collect();
+    assert_eq!(keys, ["socket.bind", "socket.dscp", "socket.read_timeout"]);
+}
+
+#[test]
+fn test_from_param_reports_bind_failure() {
+    // valid, but not an address of this host
+    let param = param("127.0.0.1:8003", "bind = \"192.0.2.1:0\"\n").unwrap();
+    assert!(LoongManiSdk::from_param(&param).is_err());
+}
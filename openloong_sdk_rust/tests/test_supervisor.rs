@@ -0,0 +1,56 @@
+use openloong_sdk_rust::sdk::arm::Side;
+use openloong_sdk_rust::sdk::ctrl::{ArmMode, CtrlData, InCharge};
+use openloong_sdk_rust::sdk::sens::SensData;
+use openloong_sdk_rust::sdk::supervisor::{Mode, ModeSupervisor, Part, Transition, transition};
+
+#[test]
+fn test_transition_rules() {
+    let none = Mode::Arm(ArmMode::None);
+    let reset = Mode::Arm(ArmMode::Reset);
+    let cart = Mode::Arm(ArmMode::CartesianBodyFrame);
+    let jnt = Mode::Arm(ArmMode::JntAxisCtrl);
+    assert_eq!(transition(none, cart), Transition::ViaReset);
+    assert_eq!(transition(none, reset), Transition::Direct);
+    assert_eq!(transition(reset, cart), Transition::Direct);
+    assert_eq!(transition(jnt, cart), Transition::Direct);
+    assert_eq!(transition(cart, none), Transition::Direct);
+}
+
+#[test]
+fn test_sequenced_entry_seeds_measured_pose() {
+    let mut ctrl = CtrlData::new(7, 6, 6, 2, 3).unwrap();
+    ctrl.set_arm_mode(ArmMode::None);
+    let mut sens = SensData::new(19, 6, 6);
+    sens.act_tip_p_rpy2b[0] = [0.3, 0.25, 0.05, 0.0, 0.1, 0.0];
+
+    let mut sup = ModeSupervisor::new(3);
+    sup.request(&ctrl, Mode::Arm(ArmMode::CartesianBodyFrame))
+        .unwrap();
+    assert_eq!(
+        sup.pending(Part::Arm),
+        vec![
+            Mode::Arm(ArmMode::Reset),
+            Mode::Arm(ArmMode::CartesianBodyFrame)
+        ]
+    );
+
+    sup.step(&mut ctrl, &sens);
+    assert_eq!(ctrl.arm_mode(), ArmMode::Reset);
+    sup.step(&mut ctrl, &sens);
+    sup.step(&mut ctrl, &sens);
+    assert_eq!(ctrl.arm_mode(), ArmMode::Reset);
+    sup.step(&mut ctrl, &sens);
+    assert_eq!(ctrl.arm_mode(), ArmMode::CartesianBodyFrame);
+    assert!(sup.is_settled());
+    assert_eq!(ctrl.arm(Side::Left).pose().xyz, [0.3, 0.25, 0.05]);
+    assert_eq!(ctrl.arm(Side::Left).pose().elbow_angle, 0.5);
+}
+
+#[test]
+fn test_tracking_rejected_when_disabled() {
+    let mut ctrl = CtrlData::new(7, 6, 6, 2, 3).unwrap();
+    ctrl.set_in_charge(InCharge::ManiCtrlDisable);
+    let mut sup = ModeSupervisor::default();
+    assert!(sup.request(&ctrl, Mode::Arm(ArmMode::JntAxisCtrl)).is_err());
+    assert!(sup.request(&ctrl, Mode::Arm(ArmMode::Reset)).is_ok());
+}
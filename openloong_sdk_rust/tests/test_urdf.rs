@@ -0,0 +1,307 @@
+use std::fmt::Write;
+
+use openloong_sdk_rust::geometry::{Pose, Shape};
+use openloong_sdk_rust::kinematics::KinematicsConfig;
+use openloong_sdk_rust::model::urdf::{Geometry, JointKind};
+use openloong_sdk_rust::model::{RobotModel, Urdf, UrdfConfig};
+use openloong_sdk_rust::param::{LoongManiParam, ParamError};
+use openloong_sdk_rust::sdk::arm::Side;
+
+fn joint(
+    out: &mut String,
+    name: &str,
+    parent: &str,
+    origin: [f64; 6],
+    axis: Option<[f64; 3]>,
+) -> String {
+    let child = format!("{}_link", name);
+    let o = origin.map(|v| v.to_string());
+    let _ = writeln!(out, "  <link name=\"{}\"/>", child);
+    match axis {
+        Some(a) => {
+            let _ = writeln!(
+                out,
+                "  <joint name=\"{name}\" type=\"revolute\">\n    <parent link=\"{parent}\"/>\
+                 <child link=\"{child}\"/>\n    <origin xyz=\"{} {} {}\" rpy=\"{} {} {}\"/>\n    \
+                 <axis xyz=\"{} {} {}\"/><limit lower=\"-2\" upper=\"1.5\" effort=\"40\" \
+                 velocity=\"3\"/>\n  </joint>",
+                o[0], o[1], o[2], o[3], o[4], o[5], a[0], a[1], a[2]
+            );
+        }
+        None => {
+            let _ = writeln!(
+                out,
+                "  <joint name=\"{name}\" type=\"fixed\">\n    <parent link=\"{parent}\"/>\
+                 <child link=\"{child}\"/>\n    <origin xyz=\"{} {} {}\" rpy=\"{} {} {}\"/>\n  </joint>",
+                o[0], o[1], o[2], o[3], o[4], o[5]
+            );
+        }
+    }
+    child
+}
+
+/// Same geometry as the built-in placeholder chains.
+fn robot() -> String {
+    let mut out = String::from("<?xml version=\"1.0\"?>\n<robot name=\"loong\">\n");
+    out.push_str(
+        "  <link name=\"base_link\">\n    <inertial><origin xyz=\"0 0 -0.1\"/>\
+         <mass value=\"12.5\"/><inertia ixx=\"0.3\" iyy=\"0.2\" izz=\"0.1\"/></inertial>\n    \
+         <collision><origin xyz=\"0 0 0.2\"/><geometry><cylinder radius=\"0.13\" \
+         length=\"0.6\"/></geometry></collision>\n  </link>\n",
+    );
+    let (x, y, z) = ([1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]);
+    let mut parent = "base_link".to_string();
+    for (i, (name, axis)) in [("lumbar_roll", x), ("lumbar_pitch", y), ("lumbar_yaw", z)]
+        .into_iter()
+        .enumerate()
+    {
+        let z = if i == 0 { -0.35 } else { 0.0 };
+        parent = joint(
+            &mut out,
+            name,
+            &parent,
+            [0.0, 0.0, z, 0.0, 0.0, 0.0],
+            Some(axis),
+        );
+    }
+    let _ = writeln!(
+        out,
+        "  <link name=\"torso_link\"/>\n  <joint name=\"torso\" type=\"fixed\"><parent link=\"{}\"/>\
+         <child link=\"torso_link\"/><origin xyz=\"0 0 0.35\"/></joint>",
+        parent
+    );
+    let neck = joint(
+        &mut out,
+        "neck_yaw",
+        "torso_link",
+        [0.0, 0.0, 0.45, 0.0, 0.0, 0.0],
+        Some(z),
+    );
+    let neck = joint(&mut out, "neck_pitch", &neck, [0.0; 6], Some(y));
+    out.push_str("  <link name=\"head_link\"/>\n");
+    let _ = writeln!(
+        out,
+        "  <joint name=\"head\" type=\"fixed\"><parent link=\"{}\"/><child link=\"head_link\"/>\
+         <origin xyz=\"0 0 0.1\"/></joint>",
+        neck
+    );
+    for (side, s) in [("left", 1.0), ("right", -1.0)] {
+        // mirrored axes on the right, as Chain::mirrored
+        let (ax, az) = ([s, 0.0, 0.0], [0.0, 0.0, s]);
+        let mut parent = "torso_link".to_string();
+        for (name, z, axis) in [
+            ("shoulder_pitch", 0.0, y),
+            ("shoulder_roll", 0.0, ax),
+            ("shoulder_yaw", 0.0, az),
+            ("elbow", -0.3, y),
+            ("forearm_yaw", 0.0, az),
+            ("wrist_pitch", -0.28, y),
+            ("wrist_roll", 0.0, ax),
+        ] {
+            let origin = if name == "shoulder_pitch" {
+                [0.0, 0.2 * s, 0.1, 0.0, 0.0, 0.0]
+            } else {
+                [0.0, 0.0, z, 0.0, 0.0, 0.0]
+            };
+            parent = joint(
+                &mut out,
+                &format!("{}_{}", side, name),
+                &parent,
+                origin,
+                Some(axis),
+            );
+        }
+        let hand = format!("{}_hand", side);
+        let pitch = std::f64::consts::FRAC_PI_2;
+        let hand_link = joint(
+            &mut out,
+            &hand,
+            &parent,
+            [0.0, 0.0, -0.1, 0.0, pitch, 0.0],
+            None,
+        );
+        let fingers = if side == "left" { 6 } else { 1 };
+        for i in 0..fingers {
+            joint(
+                &mut out,
+                &format!("{}_finger_{}", side, i),
+                &hand_link,
+                [0.0; 6],
+                Some(x),
+            );
+        }
+    }
+    out.push_str("</robot>\n");
+    out
+}
+
+fn finger_names(side: &str, n: usize) -> String {
+    (0..n)
+        .map(|i| format!("\"{}_finger_{}\"", side, i))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn urdf_table(path: &str) -> String {
+    format!(
+        "target_addr = \"127.0.0.1:8003\"\n[urdf]\npath = \"{}\"\n\
+         left_hand = \"left_hand_link\"\nright_hand = \"right_hand_link\"\n\
+         left_fingers = [{}]\nright_fingers = [{}]\n",
+        path,
+        finger_names("left", 6),
+        finger_names("right", 1)
+    )
+}
+
+fn temp_dir() -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("loong_urdf_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn test_parse_urdf() {
+    let urdf = Urdf::parse(&robot()).unwrap();
+    assert_eq!(urdf.name, "loong");
+    let base = urdf.link("base_link").unwrap();
+    let inertial = base.inertial.as_ref().unwrap();
+    assert_eq!(inertial.mass, 12.5);
+    assert_eq!(inertial.inertia, [0.3, 0.0, 0.0, 0.2, 0.0, 0.1]);
+    assert_eq!(inertial.origin.p, [0.0, 0.0, -0.1]);
+    assert_eq!(
+        base.collisions[0].geometry,
+        Geometry::Cylinder {
+            radius: 0.13,
+            length: 0.6
+        }
+    );
+    let elbow = urdf.joint("left_elbow").unwrap();
+    assert_eq!(elbow.kind, JointKind::Revolute);
+    assert_eq!(elbow.range(), [-2.0, 1.5]);
+    assert_eq!(elbow.limit.unwrap().effort, 40.0);
+    assert_eq!(urdf.joint("torso").unwrap().kind, JointKind::Fixed);
+
+    let bad = robot().replace(
+        "<parent link=\"torso_link\"/>",
+        "<parent link=\"nowhere\"/>",
+    );
+    assert!(Urdf::parse(&bad).is_err());
+    let bad = robot().replacen(
+        "<limit lower=\"-2\" upper=\"1.5\" effort=\"40\" velocity=\"3\"/>",
+        "",
+        1,
+    );
+    assert!(Urdf::parse(&bad).is_err());
+    assert!(Urdf::parse("<robot><link/></robot>").is_err());
+}
+
+#[test]
+fn test_model_matches_placeholder_chains() {
+    let config = UrdfConfig {
+        left_fingers: vec!["left_finger_0".to_string()],
+        ..Default::default()
+    };
+    let model = RobotModel::from_urdf(Urdf::parse(&robot()).unwrap(), &config).unwrap();
+    assert_eq!(model.arm_dof(), Some(7));
+    assert_eq!(
+        (model.neck_dof(), model.lumbar_dof(), model.jnt_num()),
+        (2, 3, 19)
+    );
+    let names = model.joint_names();
+    assert_eq!(names[0], "left_shoulder_pitch");
+    assert_eq!(names[13], "right_wrist_roll");
+    assert_eq!(model.joint_index("neck_yaw"), Some(14));
+    assert_eq!(model.joint_index("lumbar_yaw"), Some(18));
+    assert_eq!(model.joint_limits()[3], [-2.0, 1.5]);
+    assert_eq!(model.finger_limits(Side::Left), vec![[-2.0, 1.5]]);
+
+    // a cylinder along z around its origin
+    let shapes = model.collision_shapes("base_link");
+    let Shape::Capsule { a, b, radius } = shapes[0] else {
+        panic!("{:?}", shapes);
+    };
+    assert!((a[2] + 0.1).abs() < 1e-12 && (b[2] - 0.5).abs() < 1e-12);
+    assert_eq!((a[0], b[1], radius), (0.0, 0.0, 0.13));
+
+    let placeholder = KinematicsConfig::default();
+    let mut q = [0.0f64; 19];
+    for (i, v) in q.iter_mut().enumerate() {
+        *v = 0.1 * (i as f64 % 5.0) - 0.2;
+    }
+    for side in Side::BOTH {
+        let a = model.arm(side).tip(&Pose::IDENTITY, &q[..7]);
+        let b = placeholder.arm(side).tip(&Pose::IDENTITY, &q[..7]);
+        assert!(a.q.angle_to(&b.q) < 1e-9);
+        for i in 0..3 {
+            assert!((a.p[i] - b.p[i]).abs() < 1e-9);
+        }
+    }
+    assert!(
+        RobotModel::from_urdf(
+            Urdf::parse(&robot()).unwrap(),
+            &UrdfConfig {
+                left_fingers: vec!["left_hand".to_string()],
+                ..Default::default()
+            }
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn test_param_derives_dofs() {
+    let dir = temp_dir();
+    std::fs::write(dir.join("robot.urdf"), robot()).unwrap();
+    std::fs::write(dir.join("param.toml"), urdf_table("robot.urdf")).unwrap();
+
+    let param = LoongManiParam::load(dir.join("param.toml")).unwrap();
+    assert_eq!(param.jnt_num(), 19);
+    assert_eq!(param.arm_dof(), 7);
+    assert_eq!((param.neck_dof(), param.lumbar_dof()), (2, 3));
+    assert_eq!((param.finger_dof_left(), param.finger_dof_right()), (6, 1));
+    let model = param.model().unwrap();
+    assert_eq!(&param.kinematics().left_arm, model.arm(Side::Left));
+    assert_eq!(param.kinematics().neck.joints[1].name, "neck_pitch");
+
+    // explicit values are checked against the URDF
+    let text = format!(
+        "finger_dof_left = 1\n{}",
+        urdf_table(&dir.join("robot.urdf").display().to_string())
+    );
+    let err = LoongManiParam::from_toml_str(&text, None).unwrap_err();
+    let err = err.downcast_ref::<ParamError>().unwrap();
+    assert_eq!(err.issues().len(), 1);
+    assert_eq!(err.issues()[0].key, "urdf");
+    assert!(err.issues()[0].message.contains("finger_dof_left is 1"));
+
+    assert!(LoongManiParam::from_toml_str(&urdf_table("missing.urdf"), None).is_err());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_urdf_limits_are_checked() {
+    // own dir, test_param_derives_dofs removes temp_dir() when done
+    let dir = std::env::temp_dir().join(format!("loong_urdf_limits_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let limit = "<limit lower=\"-2\" upper=\"1.5\" effort=\"40\" velocity=\"3\"/>";
+    let inverted = robot().replacen(
+        limit,
+        "<limit lower=\"1.5\" upper=\"-2\" effort=\"40\" velocity=\"3\"/>",
+        1,
+    );
+    std::fs::write(dir.join("robot.urdf"), inverted).unwrap();
+    let text = urdf_table(&dir.join("robot.urdf").display().to_string());
+    let err = LoongManiParam::from_toml_str(&text, None).unwrap_err();
+    let err = err.downcast_ref::<ParamError>().unwrap();
+    assert_eq!(err.issues().len(), 1);
+    assert_eq!(err.issues()[0].key, "urdf");
+    assert!(err.issues()[0].message.contains("lumbar_roll"));
+
+    let nan = robot().replacen(
+        limit,
+        "<limit lower=\"NaN\" upper=\"1.5\" effort=\"40\" velocity=\"3\"/>",
+        1,
+    );
+    assert!(Urdf::parse(&nan).is_err());
+    std::fs::remove_dir_all(&dir).unwrap();
+}